@@ -0,0 +1,272 @@
+#![allow(dead_code)]
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+
+//an axis aligned box, min and max are points. infinite shapes like planes use infinite bounds
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundingBox {
+    pub min: Tuple,
+    pub max: Tuple,
+}
+
+impl BoundingBox {
+    pub fn new(min: Tuple, max: Tuple) -> BoundingBox {
+        BoundingBox { min, max }
+    }
+
+    //contains nothing, adding anything to it gives back the thing added
+    pub fn empty() -> BoundingBox {
+        BoundingBox {
+            min: Tuple::set_point(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            max: Tuple::set_point(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        }
+    }
+
+    pub fn infinite() -> BoundingBox {
+        BoundingBox {
+            min: Tuple::set_point(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            max: Tuple::set_point(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..3).any(|axis| self.min[axis] > self.max[axis])
+    }
+
+    pub fn is_finite(&self) -> bool {
+        (0..3).all(|axis| self.min[axis].is_finite() && self.max[axis].is_finite())
+    }
+
+    pub fn add_point(&mut self, point: &Tuple) {
+        self.min = Tuple::set_point(
+            self.min.x().min(point.x()),
+            self.min.y().min(point.y()),
+            self.min.z().min(point.z()),
+        );
+        self.max = Tuple::set_point(
+            self.max.x().max(point.x()),
+            self.max.y().max(point.y()),
+            self.max.z().max(point.z()),
+        );
+    }
+
+    pub fn merge(&mut self, other: &BoundingBox) {
+        if other.is_empty() {
+            return;
+        }
+        self.add_point(&other.min);
+        self.add_point(&other.max);
+    }
+
+    pub fn contains_point(&self, point: &Tuple) -> bool {
+        (0..3).all(|axis| self.min[axis] <= point[axis] && point[axis] <= self.max[axis])
+    }
+
+    //how far the point is outside the box, 0 when it's inside
+    pub fn distance_to(&self, point: &Tuple) -> f64 {
+        (0..3)
+            .map(|axis| {
+                (self.min[axis] - point[axis])
+                    .max(point[axis] - self.max[axis])
+                    .max(0.0)
+            })
+            .map(|gap| gap * gap)
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    pub fn centroid(&self) -> Tuple {
+        Tuple::set_point(
+            (self.min.x() + self.max.x()) / 2.0,
+            (self.min.y() + self.max.y()) / 2.0,
+            (self.min.z() + self.max.z()) / 2.0,
+        )
+    }
+
+    //0, 1 or 2 for x, y or z
+    pub fn longest_axis(&self) -> usize {
+        let extent: Vec<f64> = (0..3).map(|axis| self.max[axis] - self.min[axis]).collect();
+        if extent[0] >= extent[1] && extent[0] >= extent[2] {
+            0
+        } else if extent[1] >= extent[2] {
+            1
+        } else {
+            2
+        }
+    }
+
+    //bounds of all eight corners after the transform
+    pub fn transform(&self, matrix: &Matrix) -> BoundingBox {
+        if self.is_empty() {
+            return BoundingBox::empty();
+        }
+        //infinity times zero would poison the corners with NaN
+        if !self.is_finite() {
+            return BoundingBox::infinite();
+        }
+        let mut transformed = BoundingBox::empty();
+        for x in [self.min.x(), self.max.x()] {
+            for y in [self.min.y(), self.max.y()] {
+                for z in [self.min.z(), self.max.z()] {
+                    transformed.add_point(&(matrix * &Tuple::set_point(x, y, z)));
+                }
+            }
+        }
+        transformed
+    }
+
+    //tests the whole line rather than just t >= 0, the refraction walk needs hits behind the origin too
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let mut tmin = f64::NEG_INFINITY;
+        let mut tmax = f64::INFINITY;
+        for axis in 0..3 {
+            let origin = ray.origin[axis];
+            let direction = ray.direction[axis];
+            //only an exactly parallel ray can skip the division, a nearly parallel one may
+            //still cross the slab far away
+            if direction == 0.0 {
+                if origin < self.min[axis] || origin > self.max[axis] {
+                    return false;
+                }
+                continue;
+            }
+            let mut t0 = (self.min[axis] - origin) / direction;
+            let mut t1 = (self.max[axis] - origin) / direction;
+            if t0 > t1 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            tmin = tmin.max(t0);
+            tmax = tmax.min(t1);
+        }
+        tmin <= tmax
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::rotate_x;
+    use crate::transform::rotate_y;
+    use std::f64::consts::FRAC_1_SQRT_2;
+    use std::f64::consts::FRAC_PI_4;
+    use std::f64::consts::SQRT_2;
+
+    #[test]
+    fn test_empty_bounding_box() {
+        let mut bounds = BoundingBox::empty();
+        assert!(bounds.is_empty());
+        bounds.add_point(&Tuple::set_point(-5.0, 2.0, 0.0));
+        bounds.add_point(&Tuple::set_point(7.0, 0.0, -3.0));
+        assert_eq!(bounds.min, Tuple::set_point(-5.0, 0.0, -3.0));
+        assert_eq!(bounds.max, Tuple::set_point(7.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn test_merge_bounding_boxes() {
+        let mut a = BoundingBox::new(
+            Tuple::set_point(-5.0, -2.0, 0.0),
+            Tuple::set_point(7.0, 4.0, 4.0),
+        );
+        let b = BoundingBox::new(
+            Tuple::set_point(8.0, -7.0, -2.0),
+            Tuple::set_point(14.0, 2.0, 8.0),
+        );
+        a.merge(&b);
+        a.merge(&BoundingBox::empty());
+        assert_eq!(a.min, Tuple::set_point(-5.0, -7.0, -2.0));
+        assert_eq!(a.max, Tuple::set_point(14.0, 4.0, 8.0));
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let bounds = BoundingBox::new(
+            Tuple::set_point(5.0, -2.0, 0.0),
+            Tuple::set_point(11.0, 4.0, 7.0),
+        );
+        assert!(bounds.contains_point(&Tuple::set_point(5.0, -2.0, 0.0)));
+        assert!(bounds.contains_point(&Tuple::set_point(8.0, 1.0, 3.0)));
+        assert!(!bounds.contains_point(&Tuple::set_point(3.0, 0.0, 3.0)));
+        assert!(!bounds.contains_point(&Tuple::set_point(8.0, -4.0, 3.0)));
+        assert!(!bounds.contains_point(&Tuple::set_point(8.0, 1.0, 8.0)));
+    }
+
+    #[test]
+    fn test_transform_bounding_box() {
+        let bounds = BoundingBox::new(
+            Tuple::set_point(-1.0, -1.0, -1.0),
+            Tuple::set_point(1.0, 1.0, 1.0),
+        );
+        let matrix = rotate_x(FRAC_PI_4) * rotate_y(FRAC_PI_4);
+        let transformed = bounds.transform(&matrix);
+        let corner = 1.0 + FRAC_1_SQRT_2;
+        assert_eq!(transformed.min, Tuple::set_point(-SQRT_2, -corner, -corner));
+        assert_eq!(transformed.max, Tuple::set_point(SQRT_2, corner, corner));
+        assert_eq!(
+            BoundingBox::infinite().transform(&matrix),
+            BoundingBox::infinite()
+        );
+    }
+
+    #[test]
+    fn test_ray_intersects_bounding_box() {
+        let bounds = BoundingBox::new(
+            Tuple::set_point(5.0, -2.0, 0.0),
+            Tuple::set_point(11.0, 4.0, 7.0),
+        );
+        let cases = [
+            ((15.0, 1.0, 2.0), (-1.0, 0.0, 0.0), true),
+            ((-5.0, -1.0, 4.0), (1.0, 0.0, 0.0), true),
+            ((7.0, 6.0, 5.0), (0.0, -1.0, 0.0), true),
+            ((9.0, -5.0, 6.0), (0.0, 1.0, 0.0), true),
+            ((8.0, 2.0, 12.0), (0.0, 0.0, -1.0), true),
+            ((6.0, 0.0, -5.0), (0.0, 0.0, 1.0), true),
+            ((8.0, 1.0, 3.5), (0.0, 0.0, 1.0), true),
+            ((9.0, -1.0, -8.0), (2.0, 4.0, 6.0), false),
+            ((8.0, 3.0, -4.0), (6.0, 2.0, 4.0), false),
+            ((9.0, -1.0, -2.0), (4.0, 6.0, 2.0), false),
+            ((4.0, 0.0, 9.0), (0.0, 0.0, -1.0), false),
+            ((8.0, 6.0, -1.0), (0.0, -1.0, 0.0), false),
+            ((12.0, 5.0, 4.0), (-1.0, 0.0, 0.0), false),
+        ];
+        for ((ox, oy, oz), (dx, dy, dz), expected) in cases {
+            let ray = Ray::set_ray(
+                Tuple::set_vector(dx, dy, dz).normalize(),
+                Tuple::set_point(ox, oy, oz),
+            );
+            assert_eq!(bounds.intersects(&ray), expected);
+        }
+    }
+
+    #[test]
+    fn test_nearly_parallel_ray_enters_far_away() {
+        let bounds = BoundingBox::new(
+            Tuple::set_point(-1.0, -1.0, -1.0),
+            Tuple::set_point(1.0, 1.0, 1.0),
+        );
+        //starts above the box and only drops into it around t = 10000
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, -0.00005, 1.0).normalize(),
+            Tuple::set_point(0.0, 1.2, -10000.0),
+        );
+        assert!(bounds.intersects(&ray));
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.00005, 1.0).normalize(),
+            Tuple::set_point(0.0, 1.2, -10000.0),
+        );
+        assert!(!bounds.intersects(&ray));
+    }
+
+    #[test]
+    fn test_ray_intersects_infinite_bounds() {
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_point(0.0, 5.0, 0.0),
+        );
+        assert!(BoundingBox::infinite().intersects(&ray));
+        assert!(!BoundingBox::empty().intersects(&ray));
+    }
+}
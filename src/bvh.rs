@@ -0,0 +1,214 @@
+#![allow(dead_code)]
+use crate::bounds::BoundingBox;
+use crate::intersection::sort_intersections;
+use crate::intersection::Intersection;
+use crate::ray::Ray;
+use crate::shape::Shape;
+
+//nodes with this many shapes or fewer aren't split any further
+pub const LEAF_SIZE: usize = 4;
+
+#[derive(Debug)]
+enum BvhNode {
+    Leaf {
+        bounds: BoundingBox,
+        shapes: Vec<usize>,
+    },
+    Branch {
+        bounds: BoundingBox,
+        left: Box<BvhNode>,
+        right: Box<BvhNode>,
+    },
+}
+
+//a bounding volume hierarchy over a list of shapes, it stores indices into that list
+//so it has to be rebuilt whenever the list changes. shapes with infinite bounds such as
+//planes can't be split on and are tested against every ray
+#[derive(Debug)]
+pub struct Bvh {
+    root: Option<BvhNode>,
+    unbounded: Vec<usize>,
+}
+
+impl Bvh {
+    //median split along the longest axis of the shape centroids
+    pub fn build(shapes: &[Box<dyn Shape>]) -> Bvh {
+        let mut bounded = Vec::new();
+        let mut unbounded = Vec::new();
+        for (index, shape) in shapes.iter().enumerate() {
+            let bounds = shape.bounds();
+            if bounds.is_finite() {
+                bounded.push((index, bounds));
+            } else if !bounds.is_empty() {
+                unbounded.push(index);
+            }
+        }
+        let root = if bounded.is_empty() {
+            None
+        } else {
+            Some(build_node(bounded))
+        };
+        Bvh { root, unbounded }
+    }
+
+    //same intersections as testing every shape, sorted by t
+    pub fn intersect<'a>(&self, shapes: &'a [Box<dyn Shape>], ray: &Ray) -> Vec<Intersection<'a>> {
+        let mut xs = Vec::new();
+        for &index in &self.unbounded {
+            xs.extend(shapes[index].intersect(ray));
+        }
+        if let Some(root) = &self.root {
+            intersect_node(root, shapes, ray, &mut xs);
+        }
+        sort_intersections(&mut xs);
+        xs
+    }
+
+    pub fn depth(&self) -> usize {
+        self.root.as_ref().map_or(0, node_depth)
+    }
+}
+
+fn build_node(mut items: Vec<(usize, BoundingBox)>) -> BvhNode {
+    let mut bounds = BoundingBox::empty();
+    let mut centroids = BoundingBox::empty();
+    for (_, item_bounds) in &items {
+        bounds.merge(item_bounds);
+        centroids.add_point(&item_bounds.centroid());
+    }
+    let axis = centroids.longest_axis();
+    //every centroid in the same place means no split can separate them
+    if items.len() <= LEAF_SIZE || centroids.max[axis] - centroids.min[axis] <= 0.0 {
+        let shapes = items.into_iter().map(|(index, _)| index).collect();
+        return BvhNode::Leaf { bounds, shapes };
+    }
+    items.sort_by(|a, b| a.1.centroid()[axis].total_cmp(&b.1.centroid()[axis]));
+    let right = items.split_off(items.len() / 2);
+    BvhNode::Branch {
+        bounds,
+        left: Box::new(build_node(items)),
+        right: Box::new(build_node(right)),
+    }
+}
+
+fn intersect_node<'a>(
+    node: &BvhNode,
+    shapes: &'a [Box<dyn Shape>],
+    ray: &Ray,
+    xs: &mut Vec<Intersection<'a>>,
+) {
+    match node {
+        BvhNode::Leaf {
+            bounds,
+            shapes: leaf,
+        } => {
+            if bounds.intersects(ray) {
+                for &index in leaf {
+                    xs.extend(shapes[index].intersect(ray));
+                }
+            }
+        }
+        BvhNode::Branch {
+            bounds,
+            left,
+            right,
+        } => {
+            if bounds.intersects(ray) {
+                intersect_node(left, shapes, ray, xs);
+                intersect_node(right, shapes, ray, xs);
+            }
+        }
+    }
+}
+
+fn node_depth(node: &BvhNode) -> usize {
+    match node {
+        BvhNode::Leaf { .. } => 1,
+        BvhNode::Branch { left, right, .. } => 1 + node_depth(left).max(node_depth(right)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::Plane;
+    use crate::sphere::Sphere;
+    use crate::transform::translation;
+    use crate::tuple::Tuple;
+
+    fn sphere_row(count: usize) -> Vec<Box<dyn Shape>> {
+        let mut shapes: Vec<Box<dyn Shape>> = Vec::new();
+        for index in 0..count {
+            let mut sphere = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+            sphere.set_transform(translation(index as f64 * 3.0, 0.0, 0.0));
+            shapes.push(Box::new(sphere));
+        }
+        shapes
+    }
+
+    fn brute_force(shapes: &[Box<dyn Shape>], ray: &Ray) -> Vec<f64> {
+        let mut xs: Vec<Intersection> = shapes.iter().flat_map(|s| s.intersect(ray)).collect();
+        sort_intersections(&mut xs);
+        xs.iter().map(|x| x.t).collect()
+    }
+
+    #[test]
+    fn test_empty_bvh() {
+        let shapes: Vec<Box<dyn Shape>> = Vec::new();
+        let bvh = Bvh::build(&shapes);
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        assert!(bvh.intersect(&shapes, &ray).is_empty());
+        assert_eq!(bvh.depth(), 0);
+    }
+
+    #[test]
+    fn test_bvh_splits_large_lists() {
+        let shapes = sphere_row(64);
+        let bvh = Bvh::build(&shapes);
+        //64 shapes in leaves of at most 4 need at least 5 levels
+        assert!(bvh.depth() >= 5);
+    }
+
+    #[test]
+    fn test_bvh_matches_brute_force() {
+        let mut shapes = sphere_row(32);
+        shapes.push(Box::new(Plane::set_plane()));
+        let bvh = Bvh::build(&shapes);
+        let rays = [
+            Ray::set_ray(
+                Tuple::set_vector(0.0, 0.0, 1.0),
+                Tuple::set_point(9.0, 0.0, -5.0),
+            ),
+            Ray::set_ray(
+                Tuple::set_vector(1.0, 0.0, 0.0),
+                Tuple::set_point(-5.0, 0.5, 0.0),
+            ),
+            Ray::set_ray(
+                Tuple::set_vector(1.0, -1.0, 0.2).normalize(),
+                Tuple::set_point(20.0, 10.0, 0.0),
+            ),
+            Ray::set_ray(
+                Tuple::set_vector(0.0, 1.0, 0.0),
+                Tuple::set_point(1.5, 5.0, 0.0),
+            ),
+        ];
+        for ray in rays {
+            let ts: Vec<f64> = bvh.intersect(&shapes, &ray).iter().map(|x| x.t).collect();
+            assert_eq!(ts, brute_force(&shapes, &ray));
+        }
+    }
+
+    #[test]
+    fn test_bvh_keeps_hits_behind_the_ray() {
+        let shapes = sphere_row(8);
+        let bvh = Bvh::build(&shapes);
+        let ray = Ray::set_ray(
+            Tuple::set_vector(1.0, 0.0, 0.0),
+            Tuple::set_point(100.0, 0.0, 0.0),
+        );
+        assert_eq!(bvh.intersect(&shapes, &ray).len(), 16);
+    }
+}
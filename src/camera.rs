@@ -0,0 +1,228 @@
+#![allow(dead_code)]
+use crate::canvas::Canvas;
+use crate::color::Color;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use crate::world::World;
+
+#[derive(Debug, Clone)]
+pub struct Camera {
+    pub hsize: usize,
+    pub vsize: usize,
+    pub field_of_view: f64,
+    transform: Matrix,
+    inverse: Matrix,
+    half_width: f64,
+    half_height: f64,
+    pixel_size: f64,
+}
+
+impl Camera {
+    pub fn new(hsize: usize, vsize: usize, field_of_view: f64) -> Camera {
+        //the canvas sits one unit in front of the eye
+        let half_view = (field_of_view / 2.0).tan();
+        let aspect = hsize as f64 / vsize as f64;
+        let (half_width, half_height) = if aspect >= 1.0 {
+            (half_view, half_view / aspect)
+        } else {
+            (half_view * aspect, half_view)
+        };
+        Camera {
+            hsize,
+            vsize,
+            field_of_view,
+            transform: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+            half_width,
+            half_height,
+            pixel_size: (half_width * 2.0) / hsize as f64,
+        }
+    }
+
+    pub fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    pub fn set_transform(&mut self, transform: Matrix) {
+        self.inverse = transform.invert();
+        self.transform = transform;
+    }
+
+    pub fn pixel_size(&self) -> f64 {
+        self.pixel_size
+    }
+
+    //ray from the eye through the center of the pixel
+    pub fn ray_for_pixel(&self, px: usize, py: usize) -> Ray {
+        let x_offset = (px as f64 + 0.5) * self.pixel_size;
+        let y_offset = (py as f64 + 0.5) * self.pixel_size;
+        let world_x = self.half_width - x_offset;
+        let world_y = self.half_height - y_offset;
+
+        let pixel = &self.inverse * &Tuple::set_point(world_x, world_y, -1.0);
+        let origin = &self.inverse * &Tuple::set_point(0.0, 0.0, 0.0);
+        let direction = (pixel - origin.clone()).normalize();
+        Ray::set_ray(direction, origin)
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn render(camera: &Camera, world: &World) -> Canvas {
+    render_serial(camera, world)
+}
+
+//with the parallel feature the scanlines are shared across every core
+#[cfg(feature = "parallel")]
+pub fn render(camera: &Camera, world: &World) -> Canvas {
+    render_parallel(camera, world)
+}
+
+pub fn render_serial(camera: &Camera, world: &World) -> Canvas {
+    let mut image = Canvas::zero(camera.hsize, camera.vsize);
+    for y in 0..camera.vsize {
+        for (x, pixel) in render_scanline(camera, world, y).into_iter().enumerate() {
+            image.write_pixel(y, x, pixel);
+        }
+    }
+    image
+}
+
+fn render_scanline(camera: &Camera, world: &World, y: usize) -> Vec<Color> {
+    (0..camera.hsize)
+        .map(|x| world.color_at(&camera.ray_for_pixel(x, y)))
+        .collect()
+}
+
+//threads take the next free scanline until none are left. every pixel is traced exactly
+//as in render_serial, so the image is identical no matter which thread got which line
+#[cfg(feature = "parallel")]
+pub fn render_parallel(camera: &Camera, world: &World) -> Canvas {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::mpsc;
+    use std::thread;
+
+    let threads = thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+        .min(camera.vsize.max(1));
+    let next_line = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let sender = sender.clone();
+            let next_line = &next_line;
+            scope.spawn(move || loop {
+                let y = next_line.fetch_add(1, Ordering::Relaxed);
+                if y >= camera.vsize {
+                    break;
+                }
+                let pixels = render_scanline(camera, world, y);
+                if sender.send((y, pixels)).is_err() {
+                    break;
+                }
+            });
+        }
+    });
+    drop(sender);
+
+    let mut image = Canvas::zero(camera.hsize, camera.vsize);
+    for (y, pixels) in receiver {
+        for (x, pixel) in pixels.into_iter().enumerate() {
+            image.write_pixel(y, x, pixel);
+        }
+    }
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::color;
+    use crate::transform::rotate_y;
+    use crate::transform::translation;
+    use crate::transform::view_transform;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_camera_struct() {
+        let camera = Camera::new(160, 120, PI / 2.0);
+        assert_eq!(camera.hsize, 160);
+        assert_eq!(camera.vsize, 120);
+        assert_eq!(camera.field_of_view, PI / 2.0);
+        assert_eq!(*camera.transform(), Matrix::identity(4, 4));
+    }
+
+    #[test]
+    fn test_pixel_size_horizontal_canvas() {
+        let camera = Camera::new(200, 125, PI / 2.0);
+        assert!((camera.pixel_size() - 0.01).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_pixel_size_vertical_canvas() {
+        let camera = Camera::new(125, 200, PI / 2.0);
+        assert!((camera.pixel_size() - 0.01).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_ray_through_center() {
+        let camera = Camera::new(201, 101, PI / 2.0);
+        let ray = camera.ray_for_pixel(100, 50);
+        assert_eq!(ray.origin, Tuple::set_point(0.0, 0.0, 0.0));
+        assert_eq!(ray.direction, Tuple::set_vector(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_ray_through_corner() {
+        let camera = Camera::new(201, 101, PI / 2.0);
+        let ray = camera.ray_for_pixel(0, 0);
+        assert_eq!(ray.origin, Tuple::set_point(0.0, 0.0, 0.0));
+        assert_eq!(ray.direction, Tuple::set_vector(0.66519, 0.33259, -0.66851));
+    }
+
+    #[test]
+    fn test_ray_transformed_camera() {
+        let mut camera = Camera::new(201, 101, PI / 2.0);
+        camera.set_transform(rotate_y(PI / 4.0) * translation(0.0, -2.0, 5.0));
+        let ray = camera.ray_for_pixel(100, 50);
+        let root = 2.0_f64.sqrt() / 2.0;
+        assert_eq!(ray.origin, Tuple::set_point(0.0, 2.0, -5.0));
+        assert_eq!(ray.direction, Tuple::set_vector(root, 0.0, -root));
+    }
+
+    #[test]
+    fn test_render_default_world() {
+        let world = World::default_world();
+        let mut camera = Camera::new(11, 11, PI / 2.0);
+        let from = Tuple::set_point(0.0, 0.0, -5.0);
+        let to = Tuple::set_point(0.0, 0.0, 0.0);
+        let up = Tuple::set_vector(0.0, 1.0, 0.0);
+        camera.set_transform(view_transform(from, to, up));
+        let image = render(&camera, &world);
+        assert_eq!(image.read_pixel(5, 5), color(0.38066, 0.47583, 0.2855));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_render_matches_serial() {
+        let world = World::default_world();
+        let mut camera = Camera::new(33, 17, PI / 2.0);
+        let from = Tuple::set_point(0.0, 0.5, -5.0);
+        let to = Tuple::set_point(0.0, 0.0, 0.0);
+        let up = Tuple::set_vector(0.0, 1.0, 0.0);
+        camera.set_transform(view_transform(from, to, up));
+        let serial = render_serial(&camera, &world);
+        let parallel = render_parallel(&camera, &world);
+        for y in 0..camera.vsize {
+            for x in 0..camera.hsize {
+                let (a, b) = (serial.read_pixel(y, x), parallel.read_pixel(y, x));
+                assert_eq!(a.red.to_bits(), b.red.to_bits());
+                assert_eq!(a.green.to_bits(), b.green.to_bits());
+                assert_eq!(a.blue.to_bits(), b.blue.to_bits());
+            }
+        }
+    }
+}
@@ -3,10 +3,26 @@ use crate::color::color;
 use crate::color::Color;
 use float_cmp::approx_eq;
 use std::fs::File;
-use std::io::prelude;
+use std::io;
+use std::io::BufWriter;
 use std::io::Write;
 
-#[derive(Debug)]
+//plain ppm lines should be no longer than this
+pub const PPM_LINE_WIDTH: usize = 70;
+
+//the widest or tallest ppm that will be read. a zero width image still gets a row per line,
+//so its height needs a limit the pixel data can't provide
+pub const PPM_MAX_SIDE: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PpmFormat {
+    //P3, ascii numbers
+    Plain,
+    //P6, one byte per channel
+    Binary,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Canvas {
     width: usize,
     height: usize,
@@ -34,6 +50,14 @@ impl Canvas {
         }
     }
 
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
     pub fn write_pixel(&mut self, row: usize, col: usize, current_color: Color) {
         self.vector[row][col] = current_color;
     }
@@ -45,53 +69,306 @@ impl Canvas {
     pub fn print_canvas(&self) {
         for element in &self.vector {
             for color in element {
-                println!("{} {} {}", color.red, color.blue, color.green);
+                println!("{} {} {}", color.red, color.green, color.blue);
             }
         }
     }
 
-    fn scale_color(color: &Color) -> (u8, u8, u8) {
-        (
+    fn scale_color(color: &Color) -> [u8; 3] {
+        [
             Self::scale_component(color.red),
-            Self::scale_component(color.blue),
             Self::scale_component(color.green),
-        )
+            Self::scale_component(color.blue),
+        ]
     }
 
     fn scale_component(component: f64) -> u8 {
-        if component > 1.00 {
-            return (255.0) as u8;
+        (component.clamp(0.0, 1.0) * 255.0).round() as u8
+    }
+
+    //any writer will do, files are buffered by save_ppm
+    pub fn write_ppm<W: Write>(&self, mut writer: W, format: PpmFormat) -> io::Result<()> {
+        match format {
+            PpmFormat::Plain => writeln!(writer, "P3")?,
+            PpmFormat::Binary => writeln!(writer, "P6")?,
         }
+        writeln!(writer, "{} {}\n255", self.width, self.height)?;
+        for element in &self.vector {
+            match format {
+                PpmFormat::Plain => Self::write_plain_row(&mut writer, element)?,
+                PpmFormat::Binary => {
+                    for color in element {
+                        writer.write_all(&Self::scale_color(color))?;
+                    }
+                }
+            }
+        }
+        writer.flush()
+    }
 
-        if component < 0.00 {
-            return (0.0) as u8;
+    //each row starts on a new line and no line is longer than PPM_LINE_WIDTH
+    fn write_plain_row<W: Write>(writer: &mut W, row: &[Color]) -> io::Result<()> {
+        let mut line = String::new();
+        for color in row {
+            for component in Self::scale_color(color) {
+                let value = component.to_string();
+                if !line.is_empty() && line.len() + 1 + value.len() > PPM_LINE_WIDTH {
+                    writeln!(writer, "{}", line)?;
+                    line.clear();
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(&value);
+            }
         }
-        return (255.00 * component) as u8;
+        writeln!(writer, "{}", line)
     }
 
-    pub fn canvas_to_ppm(&self, name: &str, debug: bool) -> std::io::Result<()> {
-        let mut file = File::options()
-            .append(true)
-            .create_new(true)
-            .open(name)
-            .unwrap();
-        writeln!(
-            &mut file,
-            "P3\n{width} {height}\n255",
-            width = self.width,
-            height = self.height
-        );
-        for element in &self.vector {
-            for color in element {
-                let (red, blue, green) = Self::scale_color(color);
-                if debug {
-                    println!("{} {} {}", red, blue, green);
+    //overwrites name if it already exists
+    pub fn save_ppm(&self, name: &str, format: PpmFormat) -> io::Result<()> {
+        let file = File::create(name)?;
+        self.write_ppm(BufWriter::new(file), format)
+    }
+
+    pub fn canvas_to_ppm(&self, name: &str, debug: bool) -> io::Result<()> {
+        if debug {
+            self.print_canvas();
+        }
+        self.save_ppm(name, PpmFormat::Plain)
+    }
+}
+
+//everything that can go wrong reading a ppm, line is where the problem was found
+#[derive(Debug)]
+pub enum PpmError {
+    Io(io::Error),
+    //the file doesn't start with P3 or P6
+    UnsupportedFormat(String),
+    //the data ran out while looking for this
+    UnexpectedEnd(&'static str),
+    InvalidNumber {
+        line: usize,
+        found: String,
+    },
+    //maxval has to be between 1 and 65535
+    InvalidMaxval(u32),
+    //a side is over PPM_MAX_SIDE, or width times height overflows
+    TooLarge {
+        width: usize,
+        height: usize,
+    },
+    //a binary header has to end in a single whitespace byte, not a comment
+    MissingSeparator {
+        line: usize,
+    },
+    ValueOutOfRange {
+        line: usize,
+        value: u32,
+        maxval: u32,
+    },
+}
+
+impl std::fmt::Display for PpmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PpmError::Io(error) => write!(f, "could not read ppm: {}", error),
+            PpmError::UnsupportedFormat(magic) => {
+                write!(f, "'{}' is not a P3 or P6 ppm", magic)
+            }
+            PpmError::UnexpectedEnd(expected) => {
+                write!(f, "ppm ended while reading the {}", expected)
+            }
+            PpmError::InvalidNumber { line, found } => {
+                write!(f, "line {}: '{}' is not a number", line, found)
+            }
+            PpmError::InvalidMaxval(maxval) => {
+                write!(f, "maxval {} is outside 1 to 65535", maxval)
+            }
+            PpmError::TooLarge { width, height } => {
+                write!(f, "a {}x{} ppm is too large", width, height)
+            }
+            PpmError::MissingSeparator { line } => {
+                write!(
+                    f,
+                    "line {}: expected whitespace before the pixel data",
+                    line
+                )
+            }
+            PpmError::ValueOutOfRange {
+                line,
+                value,
+                maxval,
+            } => write!(f, "line {}: {} is above maxval {}", line, value, maxval),
+        }
+    }
+}
+
+impl std::error::Error for PpmError {}
+
+impl From<io::Error> for PpmError {
+    fn from(error: io::Error) -> PpmError {
+        PpmError::Io(error)
+    }
+}
+
+//walks the header and plain pixel data token by token, skipping whitespace and comments
+struct PpmReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    line: usize,
+}
+
+impl<'a> PpmReader<'a> {
+    fn skip_whitespace_and_comments(&mut self) {
+        while let Some(&byte) = self.data.get(self.position) {
+            if byte == b'#' {
+                while let Some(&byte) = self.data.get(self.position) {
+                    if byte == b'\n' {
+                        break;
+                    }
+                    self.position += 1;
+                }
+            } else if byte.is_ascii_whitespace() {
+                if byte == b'\n' {
+                    self.line += 1;
                 }
-                writeln!(&mut file, "{} {} {}\n", red, blue, green);
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn token(&mut self, expected: &'static str) -> Result<&'a [u8], PpmError> {
+        self.skip_whitespace_and_comments();
+        let start = self.position;
+        while let Some(&byte) = self.data.get(self.position) {
+            if byte.is_ascii_whitespace() || byte == b'#' {
+                break;
             }
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(PpmError::UnexpectedEnd(expected));
         }
-        writeln!(&mut file, "\n");
-        Ok(())
+        Ok(&self.data[start..self.position])
+    }
+
+    fn separator(&mut self) -> Result<(), PpmError> {
+        match self.data.get(self.position) {
+            Some(byte) if byte.is_ascii_whitespace() => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(_) => Err(PpmError::MissingSeparator { line: self.line }),
+            None => Err(PpmError::UnexpectedEnd("pixel data")),
+        }
+    }
+
+    fn number(&mut self, expected: &'static str) -> Result<u32, PpmError> {
+        let token = self.token(expected)?;
+        let text = String::from_utf8_lossy(token);
+        text.parse().map_err(|_| PpmError::InvalidNumber {
+            line: self.line,
+            found: text.into_owned(),
+        })
+    }
+
+    fn sample(&mut self, maxval: u32) -> Result<f64, PpmError> {
+        let value = self.number("pixel data")?;
+        check_sample(value, maxval, self.line)
+    }
+
+    //binary samples are one byte, or two big endian bytes when maxval is above 255
+    fn binary_sample(&mut self, maxval: u32) -> Result<f64, PpmError> {
+        let width = if maxval > 255 { 2 } else { 1 };
+        let bytes = self
+            .data
+            .get(self.position..self.position + width)
+            .ok_or(PpmError::UnexpectedEnd("pixel data"))?;
+        let value = bytes
+            .iter()
+            .fold(0, |total, &byte| (total << 8) | byte as u32);
+        self.position += width;
+        check_sample(value, maxval, self.line)
+    }
+}
+
+fn check_sample(value: u32, maxval: u32, line: usize) -> Result<f64, PpmError> {
+    if value > maxval {
+        return Err(PpmError::ValueOutOfRange {
+            line,
+            value,
+            maxval,
+        });
+    }
+    Ok(value as f64 / maxval as f64)
+}
+
+impl Canvas {
+    //reads P3 or P6 with any maxval, samples are scaled to 0..1
+    pub fn from_ppm(data: &[u8]) -> Result<Canvas, PpmError> {
+        let mut reader = PpmReader {
+            data,
+            position: 0,
+            line: 1,
+        };
+        let format = match reader.token("magic number")? {
+            b"P3" => PpmFormat::Plain,
+            b"P6" => PpmFormat::Binary,
+            other => {
+                return Err(PpmError::UnsupportedFormat(
+                    String::from_utf8_lossy(other).into_owned(),
+                ))
+            }
+        };
+        let width = reader.number("width")? as usize;
+        let height = reader.number("height")? as usize;
+        let maxval = reader.number("maxval")?;
+        if maxval == 0 || maxval > 65535 {
+            return Err(PpmError::InvalidMaxval(maxval));
+        }
+        if format == PpmFormat::Binary {
+            //exactly one whitespace byte separates the header from the raster
+            reader.separator()?;
+        }
+        if width > PPM_MAX_SIDE || height > PPM_MAX_SIDE {
+            return Err(PpmError::TooLarge { width, height });
+        }
+        //every sample takes at least a byte, so a header claiming more pixels than the data
+        //could hold fails here instead of allocating
+        let bytes_per_sample = match format {
+            PpmFormat::Binary if maxval > 255 => 2,
+            _ => 1,
+        };
+        let needed = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3 * bytes_per_sample))
+            .ok_or(PpmError::TooLarge { width, height })?;
+        if needed > data.len() - reader.position {
+            return Err(PpmError::UnexpectedEnd("pixel data"));
+        }
+
+        let mut canvas = Canvas::zero(width, height);
+        for row in 0..height {
+            for col in 0..width {
+                let mut channels = [0.0; 3];
+                for channel in channels.iter_mut() {
+                    *channel = match format {
+                        PpmFormat::Plain => reader.sample(maxval)?,
+                        PpmFormat::Binary => reader.binary_sample(maxval)?,
+                    };
+                }
+                canvas.write_pixel(row, col, color(channels[0], channels[1], channels[2]));
+            }
+        }
+        Ok(canvas)
+    }
+
+    pub fn load_ppm(name: &str) -> Result<Canvas, PpmError> {
+        let data = std::fs::read(name)?;
+        Canvas::from_ppm(&data)
     }
 }
 
@@ -106,4 +383,236 @@ mod tests {
         canvas.write_pixel(0, 0, red.clone());
         assert_eq!(red, canvas.read_pixel(0, 0));
     }
+
+    fn plain_ppm(canvas: &Canvas) -> String {
+        let mut output = Vec::new();
+        canvas.write_ppm(&mut output, PpmFormat::Plain).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_ppm_header() {
+        let canvas = Canvas::zero(5, 3);
+        let ppm = plain_ppm(&canvas);
+        let header: Vec<&str> = ppm.lines().take(3).collect();
+        assert_eq!(header, vec!["P3", "5 3", "255"]);
+    }
+
+    #[test]
+    fn test_ppm_pixel_data() {
+        let mut canvas = Canvas::zero(5, 3);
+        canvas.write_pixel(0, 0, color(1.5, 0.0, 0.0));
+        canvas.write_pixel(1, 2, color(0.0, 0.5, 0.0));
+        canvas.write_pixel(2, 4, color(-0.5, 0.0, 1.0));
+        let ppm = plain_ppm(&canvas);
+        let lines: Vec<&str> = ppm.lines().skip(3).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "255 0 0 0 0 0 0 0 0 0 0 0 0 0 0",
+                "0 0 0 0 0 0 0 128 0 0 0 0 0 0 0",
+                "0 0 0 0 0 0 0 0 0 0 0 0 0 0 255",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ppm_splits_long_lines() {
+        let mut canvas = Canvas::zero(10, 2);
+        for row in 0..2 {
+            for col in 0..10 {
+                canvas.write_pixel(row, col, color(1.0, 0.8, 0.6));
+            }
+        }
+        let ppm = plain_ppm(&canvas);
+        let lines: Vec<&str> = ppm.lines().skip(3).collect();
+        let long = "255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 204";
+        let short = "153 255 204 153 255 204 153 255 204 153 255 204 153";
+        assert_eq!(lines, vec![long, short, long, short]);
+        assert!(lines.iter().all(|line| line.len() <= PPM_LINE_WIDTH));
+    }
+
+    #[test]
+    fn test_ppm_ends_with_newline() {
+        let canvas = Canvas::zero(5, 3);
+        assert!(plain_ppm(&canvas).ends_with('\n'));
+    }
+
+    #[test]
+    fn test_binary_ppm() {
+        let mut canvas = Canvas::zero(2, 1);
+        canvas.write_pixel(0, 0, color(1.0, 0.5, 0.0));
+        canvas.write_pixel(0, 1, color(0.0, 0.2, 1.0));
+        let mut output = Vec::new();
+        canvas.write_ppm(&mut output, PpmFormat::Binary).unwrap();
+        let mut expected = b"P6\n2 1\n255\n".to_vec();
+        expected.extend([255, 128, 0, 0, 51, 255]);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_save_ppm_overwrites() {
+        let path = std::env::temp_dir().join(format!("canvas_{}.ppm", std::process::id()));
+        let name = path.to_str().unwrap();
+        Canvas::zero(4, 4).save_ppm(name, PpmFormat::Plain).unwrap();
+        Canvas::zero(1, 1).save_ppm(name, PpmFormat::Plain).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(written, "P3\n1 1\n255\n0 0 0\n");
+    }
+
+    #[test]
+    fn test_save_ppm_reports_errors() {
+        let canvas = Canvas::zero(1, 1);
+        let missing = std::env::temp_dir().join("no_such_directory_for_ppm/out.ppm");
+        assert!(canvas
+            .save_ppm(missing.to_str().unwrap(), PpmFormat::Plain)
+            .is_err());
+    }
+
+    #[test]
+    fn test_read_plain_ppm() {
+        let ppm = b"P3\n# made by hand\n4 3\n255\n\
+                    255 127 0  0 127 255  127 255 0  255 255 255\n\
+                    0 0 0  255 0 0  0 255 0  0 0 255\n\
+                    255 255 0  0 255 255  255 0 255  127 127 127\n";
+        let canvas = Canvas::from_ppm(ppm).unwrap();
+        assert_eq!(canvas.width(), 4);
+        assert_eq!(canvas.height(), 3);
+        assert_eq!(canvas.read_pixel(0, 0), color(1.0, 127.0 / 255.0, 0.0));
+        assert_eq!(canvas.read_pixel(0, 3), color(1.0, 1.0, 1.0));
+        assert_eq!(canvas.read_pixel(1, 1), color(1.0, 0.0, 0.0));
+        assert_eq!(canvas.read_pixel(2, 1), color(0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_read_ppm_comments_and_whitespace() {
+        let ppm = b"P3 # a comment after the magic\n\
+                    # a whole comment line\n\
+                    2\t1\r\n  100 # maxval other than 255\n\
+                    100 50 0\n\n  25\n 75 100";
+        let canvas = Canvas::from_ppm(ppm).unwrap();
+        assert_eq!(canvas.read_pixel(0, 0), color(1.0, 0.5, 0.0));
+        assert_eq!(canvas.read_pixel(0, 1), color(0.25, 0.75, 1.0));
+    }
+
+    #[test]
+    fn test_read_binary_ppm() {
+        let mut ppm = b"P6 # binary\n2 1\n255\n".to_vec();
+        ppm.extend([255, 0, 51, 10, 32, 255]);
+        let canvas = Canvas::from_ppm(&ppm).unwrap();
+        assert_eq!(canvas.read_pixel(0, 0), color(1.0, 0.0, 0.2));
+        assert_eq!(
+            canvas.read_pixel(0, 1),
+            color(10.0 / 255.0, 32.0 / 255.0, 1.0)
+        );
+
+        //two bytes per sample above 255
+        let mut wide = b"P6\n1 1\n65535\n".to_vec();
+        wide.extend([0xff, 0xff, 0x80, 0x00, 0x00, 0x00]);
+        let canvas = Canvas::from_ppm(&wide).unwrap();
+        assert_eq!(canvas.read_pixel(0, 0), color(1.0, 32768.0 / 65535.0, 0.0));
+    }
+
+    #[test]
+    fn test_ppm_round_trip() {
+        let mut canvas = Canvas::zero(12, 3);
+        for row in 0..3 {
+            for col in 0..12 {
+                let value = (row * 12 + col) as f64 * 5.0 / 255.0;
+                canvas.write_pixel(row, col, color(value, 1.0 - value, 0.2));
+            }
+        }
+        for format in [PpmFormat::Plain, PpmFormat::Binary] {
+            let mut output = Vec::new();
+            canvas.write_ppm(&mut output, format).unwrap();
+            assert_eq!(Canvas::from_ppm(&output).unwrap(), canvas);
+        }
+    }
+
+    #[test]
+    fn test_ppm_errors() {
+        assert!(matches!(
+            Canvas::from_ppm(b"P5\n1 1\n255\n0"),
+            Err(PpmError::UnsupportedFormat(magic)) if magic == "P5"
+        ));
+        assert!(matches!(
+            Canvas::from_ppm(b""),
+            Err(PpmError::UnexpectedEnd("magic number"))
+        ));
+        assert!(matches!(
+            Canvas::from_ppm(b"P3\n2 1\n255\n0 0 0 0 0"),
+            Err(PpmError::UnexpectedEnd("pixel data"))
+        ));
+        assert!(matches!(
+            Canvas::from_ppm(b"P3\n1 1\n255\n0\n0 x"),
+            Err(PpmError::InvalidNumber { line: 5, found }) if found == "x"
+        ));
+        assert!(matches!(
+            Canvas::from_ppm(b"P3\n1 1\n0\n0 0 0"),
+            Err(PpmError::InvalidMaxval(0))
+        ));
+        assert!(matches!(
+            Canvas::from_ppm(b"P3\n1 1\n15\n0 16 0"),
+            Err(PpmError::ValueOutOfRange {
+                line: 4,
+                value: 16,
+                maxval: 15
+            })
+        ));
+        assert!(matches!(
+            Canvas::from_ppm(b"P6\n1 1\n255#comment\n\x01\x02\x03"),
+            Err(PpmError::MissingSeparator { line: 3 })
+        ));
+        assert!(matches!(
+            Canvas::from_ppm(b"P6\n1 1\n255"),
+            Err(PpmError::UnexpectedEnd("pixel data"))
+        ));
+        assert!(matches!(
+            Canvas::from_ppm(b"P6\n4294967295 4294967295\n255\n\x01\x02\x03"),
+            Err(PpmError::TooLarge {
+                width: 4294967295,
+                height: 4294967295
+            })
+        ));
+        assert!(matches!(
+            Canvas::from_ppm(b"P6\n100000 100000\n255\n\x01\x02\x03"),
+            Err(PpmError::UnexpectedEnd("pixel data"))
+        ));
+        assert!(matches!(
+            Canvas::from_ppm(b"P3\n0 4294967295\n255\n"),
+            Err(PpmError::TooLarge { .. })
+        ));
+        let mut short = b"P6\n2 1\n255\n".to_vec();
+        short.extend([1, 2, 3, 4]);
+        assert!(matches!(
+            Canvas::from_ppm(&short),
+            Err(PpmError::UnexpectedEnd("pixel data"))
+        ));
+    }
+
+    #[test]
+    fn test_read_zero_area_ppm() {
+        let canvas = Canvas::from_ppm(b"P6\n0 3\n255\n").unwrap();
+        assert_eq!(canvas, Canvas::zero(0, 3));
+        let canvas = Canvas::from_ppm(b"P3\n4 0\n255\n").unwrap();
+        assert_eq!((canvas.width(), canvas.height()), (4, 0));
+    }
+
+    #[test]
+    fn test_load_ppm() {
+        let path = std::env::temp_dir().join(format!("canvas_load_{}.ppm", std::process::id()));
+        let name = path.to_str().unwrap();
+        let mut canvas = Canvas::zero(3, 2);
+        canvas.write_pixel(1, 2, color(1.0, 0.2, 0.0));
+        canvas.save_ppm(name, PpmFormat::Binary).unwrap();
+        let loaded = Canvas::load_ppm(name);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), canvas);
+
+        let missing = std::env::temp_dir().join("no_such_directory_for_ppm/in.ppm");
+        let error = Canvas::load_ppm(missing.to_str().unwrap()).unwrap_err();
+        assert!(matches!(error, PpmError::Io(_)));
+        assert!(error.to_string().starts_with("could not read ppm"));
+    }
 }
@@ -0,0 +1,212 @@
+#![allow(dead_code)]
+use crate::bounds::BoundingBox;
+use crate::cylinder::check_cap;
+use crate::intersection::Intersection;
+use crate::intersection::EPSILON;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+//two cones meeting tip to tip at the origin, radius at any height is |y|
+#[derive(Debug, Clone)]
+pub struct DoubleCone {
+    transform: Matrix,
+    inverse: Matrix,
+    pub material: Material,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool,
+}
+
+impl DoubleCone {
+    pub fn set_double_cone() -> DoubleCone {
+        DoubleCone {
+            transform: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+            material: Material::default_material(),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+
+    pub fn set_truncated(minimum: f64, maximum: f64, closed: bool) -> DoubleCone {
+        let mut cone = DoubleCone::set_double_cone();
+        cone.minimum = minimum;
+        cone.maximum = maximum;
+        cone.closed = closed;
+        cone
+    }
+
+    fn intersect_caps<'a>(&'a self, ray: &Ray, xs: &mut Vec<Intersection<'a>>) {
+        if !self.closed || ray.direction.y().abs() < EPSILON {
+            return;
+        }
+        for cap in [self.minimum, self.maximum] {
+            let t = (cap - ray.origin.y()) / ray.direction.y();
+            if check_cap(ray, t, cap.abs()) {
+                xs.push(Intersection::new(t, self));
+            }
+        }
+    }
+
+    fn push_if_in_bounds<'a>(&'a self, ray: &Ray, t: f64, xs: &mut Vec<Intersection<'a>>) {
+        let y = ray.origin.y() + t * ray.direction.y();
+        if self.minimum < y && y < self.maximum {
+            xs.push(Intersection::new(t, self));
+        }
+    }
+}
+
+impl Shape for DoubleCone {
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut xs = Vec::new();
+        let (dx, dy, dz) = (ray.direction.x(), ray.direction.y(), ray.direction.z());
+        let (ox, oy, oz) = (ray.origin.x(), ray.origin.y(), ray.origin.z());
+
+        let a = dx * dx - dy * dy + dz * dz;
+        let b = 2.0 * ox * dx - 2.0 * oy * dy + 2.0 * oz * dz;
+        let c = ox * ox - oy * oy + oz * oz;
+
+        if a.abs() < EPSILON {
+            //parallel to one of the halves, so it crosses the other half once
+            if b.abs() >= EPSILON {
+                self.push_if_in_bounds(ray, -c / (2.0 * b), &mut xs);
+            }
+        } else {
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant < 0.0 {
+                return xs;
+            }
+            let mut t0 = (-b - discriminant.sqrt()) / (2.0 * a);
+            let mut t1 = (-b + discriminant.sqrt()) / (2.0 * a);
+            if t0 > t1 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            self.push_if_in_bounds(ray, t0, &mut xs);
+            self.push_if_in_bounds(ray, t1, &mut xs);
+        }
+
+        self.intersect_caps(ray, &mut xs);
+        xs
+    }
+
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let distance = point.x() * point.x() + point.z() * point.z();
+        let max_radius = self.maximum * self.maximum;
+        let min_radius = self.minimum * self.minimum;
+        if distance < max_radius && point.y() >= self.maximum - EPSILON {
+            return Tuple::set_vector(0.0, 1.0, 0.0);
+        }
+        if distance < min_radius && point.y() <= self.minimum + EPSILON {
+            return Tuple::set_vector(0.0, -1.0, 0.0);
+        }
+
+        let mut y = distance.sqrt();
+        if point.y() > 0.0 {
+            y = -y;
+        }
+        Tuple::set_vector(point.x(), y, point.z())
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        //the radius at any height is the distance from the apex
+        let limit = self.minimum.abs().max(self.maximum.abs());
+        BoundingBox::new(
+            Tuple::set_point(-limit, self.minimum, -limit),
+            Tuple::set_point(limit, self.maximum, limit),
+        )
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn inverse(&self) -> &Matrix {
+        &self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.inverse = transform.invert();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cast(cone: &DoubleCone, origin: (f64, f64, f64), direction: (f64, f64, f64)) -> Vec<f64> {
+        let ray = Ray::set_ray(
+            Tuple::set_vector(direction.0, direction.1, direction.2).normalize(),
+            Tuple::set_point(origin.0, origin.1, origin.2),
+        );
+        cone.local_intersect(&ray).iter().map(|i| i.t).collect()
+    }
+
+    #[test]
+    fn test_ray_strikes_cone() {
+        let cone = DoubleCone::set_double_cone();
+        let cases = [
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+            ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0), 8.66025, 8.66025),
+            ((1.0, 1.0, -5.0), (-0.5, -1.0, 1.0), 4.55006, 49.44994),
+        ];
+        for (origin, direction, t0, t1) in cases {
+            let xs = cast(&cone, origin, direction);
+            assert_eq!(xs.len(), 2);
+            assert!((xs[0] - t0).abs() < 0.0001);
+            assert!((xs[1] - t1).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_ray_parallel_to_one_half() {
+        let cone = DoubleCone::set_double_cone();
+        let xs = cast(&cone, (0.0, 0.0, -1.0), (0.0, 1.0, 1.0));
+        assert_eq!(xs.len(), 1);
+        assert!((xs[0] - 0.35355).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_cone_end_caps() {
+        let cone = DoubleCone::set_truncated(-0.5, 0.5, true);
+        let cases = [
+            ((0.0, 0.0, -5.0), (0.0, 1.0, 0.0), 0),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 1.0), 2),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 0.0), 4),
+        ];
+        for (origin, direction, count) in cases {
+            assert_eq!(cast(&cone, origin, direction).len(), count);
+        }
+    }
+
+    #[test]
+    fn test_cone_normal() {
+        let cone = DoubleCone::set_double_cone();
+        let cases = [
+            ((0.0, 0.0, 0.0), (0.0, 0.0, 0.0)),
+            ((1.0, 1.0, 1.0), (1.0, -(2.0_f64.sqrt()), 1.0)),
+            ((-1.0, -1.0, 0.0), (-1.0, 1.0, 0.0)),
+        ];
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            let normal = cone.local_normal_at(Tuple::set_point(px, py, pz));
+            assert_eq!(normal, Tuple::set_vector(nx, ny, nz));
+        }
+    }
+
+    #[test]
+    fn test_cone_cap_normals() {
+        let cone = DoubleCone::set_truncated(-1.0, 2.0, true);
+        let top = cone.local_normal_at(Tuple::set_point(0.5, 2.0, 0.5));
+        let bottom = cone.local_normal_at(Tuple::set_point(0.2, -1.0, 0.3));
+        assert_eq!(top, Tuple::set_vector(0.0, 1.0, 0.0));
+        assert_eq!(bottom, Tuple::set_vector(0.0, -1.0, 0.0));
+    }
+}
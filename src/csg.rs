@@ -0,0 +1,320 @@
+#![allow(dead_code)]
+use crate::bounds::BoundingBox;
+use crate::intersection::sort_intersections;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::shape::nearest_child;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsgOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+//lhit is whether the left shape was hit, inl and inr whether the ray is currently inside each side
+pub fn intersection_allowed(operation: CsgOperation, lhit: bool, inl: bool, inr: bool) -> bool {
+    match operation {
+        CsgOperation::Union => (lhit && !inr) || (!lhit && !inl),
+        CsgOperation::Intersection => (lhit && inr) || (!lhit && inl),
+        CsgOperation::Difference => (lhit && !inr) || (!lhit && inl),
+    }
+}
+
+//a boolean combination of two shapes. like a group the csg transform is baked into both
+//sides, and hits keep pointing at the primitive that was hit so it shades with its own material
+#[derive(Debug)]
+pub struct Csg {
+    pub operation: CsgOperation,
+    left: Box<dyn Shape>,
+    right: Box<dyn Shape>,
+    transform: Matrix,
+    inverse: Matrix,
+    material: Material,
+}
+
+impl Csg {
+    pub fn set_csg(operation: CsgOperation, left: Box<dyn Shape>, right: Box<dyn Shape>) -> Csg {
+        Csg {
+            operation,
+            left,
+            right,
+            transform: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+            material: Material::default_material(),
+        }
+    }
+
+    pub fn left(&self) -> &dyn Shape {
+        self.left.as_ref()
+    }
+
+    pub fn right(&self) -> &dyn Shape {
+        self.right.as_ref()
+    }
+
+    //ray is in world space, where both sides are
+    fn intersect_sides(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut xs = self.left.intersect(ray);
+        xs.extend(self.right.intersect(ray));
+        sort_intersections(&mut xs);
+        self.filter_intersections(xs)
+    }
+
+    //xs has to be sorted, walking it tracks whether the ray is inside each side
+    pub fn filter_intersections<'a>(&self, xs: Vec<Intersection<'a>>) -> Vec<Intersection<'a>> {
+        let mut inl = false;
+        let mut inr = false;
+        let mut result = Vec::new();
+        for x in xs {
+            let lhit = self.left.includes(x.object);
+            if intersection_allowed(self.operation, lhit, inl, inr) {
+                result.push(x);
+            }
+            if lhit {
+                inl = !inl;
+            } else {
+                inr = !inr;
+            }
+        }
+        result
+    }
+}
+
+impl Shape for Csg {
+    //both sides are in world space, so the local ray is taken back out to meet them
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        self.intersect_sides(&ray.transform(&self.transform))
+    }
+
+    //same as a group, out to world space and back
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let normal = self.normal_at(&self.transform * &point);
+        let local = &self.transform.transpose() * &normal;
+        Tuple::set_vector(local.x(), local.y(), local.z())
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn inverse(&self) -> &Matrix {
+        &self.inverse
+    }
+
+    //swap the old transform for the new one on both sides
+    fn set_transform(&mut self, transform: Matrix) {
+        let change = &transform * &self.inverse;
+        let left = &change * self.left.transform();
+        self.left.set_transform(left);
+        let right = &change * self.right.transform();
+        self.right.set_transform(right);
+        self.inverse = transform.invert();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        self.bounds().transform(&self.inverse)
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = self.left.bounds();
+        bounds.merge(&self.right.bounds());
+        bounds
+    }
+
+    fn includes(&self, other: &dyn Shape) -> bool {
+        self.left.includes(other) || self.right.includes(other)
+    }
+
+    //skips the round trip through local space
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        self.intersect_sides(ray)
+    }
+
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        let sides = [self.left.as_ref(), self.right.as_ref()];
+        //there are always two sides to pick from
+        nearest_child(sides, &point).unwrap().normal_at(point)
+    }
+
+    fn normal_at_hit(&self, point: Tuple, hit: &Intersection) -> Tuple {
+        if self.includes(hit.object) {
+            hit.object.normal_at_hit(point, hit)
+        } else {
+            self.normal_at(point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Cube;
+    use crate::intersection::same_object;
+    use crate::sphere::Sphere;
+    use crate::transform::translation;
+
+    #[test]
+    fn test_intersection_allowed_truth_table() {
+        use CsgOperation::*;
+        let table = [
+            (Union, [true, false, true, false, true, true, false, false]),
+            (
+                Intersection,
+                [false, true, false, true, false, false, true, true],
+            ),
+            (
+                Difference,
+                [true, false, true, false, false, false, true, true],
+            ),
+        ];
+        for (operation, expected) in table {
+            for (index, allowed) in expected.iter().enumerate() {
+                let lhit = index < 4;
+                let inl = index % 4 >= 2;
+                let inr = index % 2 == 1;
+                assert_eq!(
+                    intersection_allowed(operation, lhit, inl, inr),
+                    *allowed,
+                    "{:?} lhit={} inl={} inr={}",
+                    operation,
+                    lhit,
+                    inl,
+                    inr
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_filter_intersections() {
+        let cases = [
+            (CsgOperation::Union, 0, 3),
+            (CsgOperation::Intersection, 1, 2),
+            (CsgOperation::Difference, 0, 1),
+        ];
+        for (operation, x0, x1) in cases {
+            let csg = Csg::set_csg(
+                operation,
+                Box::new(Sphere::unit()),
+                Box::new(Cube::set_cube()),
+            );
+            let xs = vec![
+                Intersection::new(1.0, csg.left()),
+                Intersection::new(2.0, csg.right()),
+                Intersection::new(3.0, csg.left()),
+                Intersection::new(4.0, csg.right()),
+            ];
+            let expected = [xs[x0].t, xs[x1].t];
+            let result = csg.filter_intersections(xs);
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0].t, expected[0]);
+            assert_eq!(result[1].t, expected[1]);
+        }
+    }
+
+    #[test]
+    fn test_ray_misses_csg() {
+        let csg = Csg::set_csg(
+            CsgOperation::Union,
+            Box::new(Sphere::unit()),
+            Box::new(Cube::set_cube()),
+        );
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 2.0, -5.0),
+        );
+        assert!(csg.intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn test_ray_hits_csg() {
+        let mut right = Sphere::unit();
+        right.set_transform(translation(0.0, 0.0, 0.5));
+        let csg = Csg::set_csg(
+            CsgOperation::Union,
+            Box::new(Sphere::unit()),
+            Box::new(right),
+        );
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let xs = csg.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].t, 4.0);
+        assert!(same_object(xs[0].object, csg.left()));
+        assert_eq!(xs[1].t, 6.5);
+        assert!(same_object(xs[1].object, csg.right()));
+    }
+
+    #[test]
+    fn test_difference_carves_out_and_transforms() {
+        //a cube with a sphere carved out of its front face
+        let mut sphere = Sphere::unit();
+        sphere.set_transform(translation(0.0, 0.0, -1.0));
+        let mut csg = Csg::set_csg(
+            CsgOperation::Difference,
+            Box::new(Cube::set_cube()),
+            Box::new(sphere),
+        );
+        csg.set_transform(translation(0.0, 0.0, 10.0));
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, 0.0),
+        );
+        let xs = csg.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].t, 10.0);
+        assert!(same_object(xs[0].object, csg.right()));
+        assert_eq!(xs[1].t, 11.0);
+        assert!(same_object(xs[1].object, csg.left()));
+        //the carved hit uses the sphere's normal, pointing into the hollow
+        let normal = xs[0].object.normal_at(Tuple::set_point(0.0, 0.0, 10.0));
+        assert_eq!(normal, Tuple::set_vector(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_csg_normal_comes_from_sides() {
+        let mut right = Sphere::unit();
+        right.set_transform(translation(0.0, 0.0, 3.0));
+        let mut csg = Csg::set_csg(
+            CsgOperation::Union,
+            Box::new(Cube::set_cube()),
+            Box::new(right),
+        );
+        csg.set_transform(translation(5.0, 0.0, 0.0));
+        assert_eq!(
+            csg.normal_at(Tuple::set_point(6.0, 0.5, 0.0)),
+            Tuple::set_vector(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            csg.normal_at(Tuple::set_point(5.0, 0.0, 4.0)),
+            Tuple::set_vector(0.0, 0.0, 1.0)
+        );
+        //in local space the cube's face points the same way
+        assert_eq!(
+            csg.local_normal_at(Tuple::set_point(1.0, 0.5, 0.0)),
+            Tuple::set_vector(1.0, 0.0, 0.0)
+        );
+        //a hit on the csg is shaded by the side it landed on
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, -1.0),
+            Tuple::set_point(5.0, 0.0, 10.0),
+        );
+        let xs = csg.intersect(&ray);
+        assert_eq!(
+            csg.normal_at_hit(Tuple::set_point(5.0, 0.0, 4.0), &xs[0]),
+            Tuple::set_vector(0.0, 0.0, 1.0)
+        );
+    }
+}
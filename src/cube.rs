@@ -0,0 +1,182 @@
+#![allow(dead_code)]
+use crate::bounds::BoundingBox;
+use crate::intersection::Intersection;
+use crate::intersection::EPSILON;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+//axis aligned box from -1 to 1 on every axis in object space
+#[derive(Debug, Clone)]
+pub struct Cube {
+    transform: Matrix,
+    inverse: Matrix,
+    pub material: Material,
+}
+
+impl Cube {
+    pub fn set_cube() -> Cube {
+        Cube {
+            transform: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+            material: Material::default_material(),
+        }
+    }
+}
+
+//where the ray enters and leaves the slab between -1 and 1 on one axis
+pub fn check_axis(origin: f64, direction: f64) -> (f64, f64) {
+    let tmin_numerator = -1.0 - origin;
+    let tmax_numerator = 1.0 - origin;
+
+    let (tmin, tmax) = if direction.abs() >= EPSILON {
+        (tmin_numerator / direction, tmax_numerator / direction)
+    } else {
+        (
+            tmin_numerator * f64::INFINITY,
+            tmax_numerator * f64::INFINITY,
+        )
+    };
+
+    if tmin > tmax {
+        (tmax, tmin)
+    } else {
+        (tmin, tmax)
+    }
+}
+
+impl Shape for Cube {
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let (xtmin, xtmax) = check_axis(ray.origin.x(), ray.direction.x());
+        let (ytmin, ytmax) = check_axis(ray.origin.y(), ray.direction.y());
+        let (ztmin, ztmax) = check_axis(ray.origin.z(), ray.direction.z());
+
+        //the ray is inside the box only where it is inside all three slabs
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+        if tmin > tmax {
+            return Vec::new();
+        }
+        vec![Intersection::new(tmin, self), Intersection::new(tmax, self)]
+    }
+
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let (x, y, z) = (point.x().abs(), point.y().abs(), point.z().abs());
+        let max_component = x.max(y).max(z);
+
+        if max_component == x {
+            Tuple::set_vector(point.x(), 0.0, 0.0)
+        } else if max_component == y {
+            Tuple::set_vector(0.0, point.y(), 0.0)
+        } else {
+            Tuple::set_vector(0.0, 0.0, point.z())
+        }
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Tuple::set_point(-1.0, -1.0, -1.0),
+            Tuple::set_point(1.0, 1.0, 1.0),
+        )
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn inverse(&self) -> &Matrix {
+        &self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.inverse = transform.invert();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::scaling;
+    use crate::transform::translation;
+
+    #[test]
+    fn test_ray_intersects_cube() {
+        let cube = Cube::set_cube();
+        let cases = [
+            ((5.0, 0.5, 0.0), (-1.0, 0.0, 0.0), 4.0, 6.0),
+            ((-5.0, 0.5, 0.0), (1.0, 0.0, 0.0), 4.0, 6.0),
+            ((0.5, 5.0, 0.0), (0.0, -1.0, 0.0), 4.0, 6.0),
+            ((0.5, -5.0, 0.0), (0.0, 1.0, 0.0), 4.0, 6.0),
+            ((0.5, 0.0, 5.0), (0.0, 0.0, -1.0), 4.0, 6.0),
+            ((0.5, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+            ((0.0, 0.5, 0.0), (0.0, 0.0, 1.0), -1.0, 1.0),
+        ];
+        for ((ox, oy, oz), (dx, dy, dz), t1, t2) in cases {
+            let ray = Ray::set_ray(Tuple::set_vector(dx, dy, dz), Tuple::set_point(ox, oy, oz));
+            let xs = cube.local_intersect(&ray);
+            assert_eq!(xs.len(), 2);
+            assert_eq!(xs[0].t, t1);
+            assert_eq!(xs[1].t, t2);
+        }
+    }
+
+    #[test]
+    fn test_ray_misses_cube() {
+        let cube = Cube::set_cube();
+        let cases = [
+            ((-2.0, 0.0, 0.0), (0.2673, 0.5345, 0.8018)),
+            ((0.0, -2.0, 0.0), (0.8018, 0.2673, 0.5345)),
+            ((0.0, 0.0, -2.0), (0.5345, 0.8018, 0.2673)),
+            ((2.0, 0.0, 2.0), (0.0, 0.0, -1.0)),
+            ((0.0, 2.0, 2.0), (0.0, -1.0, 0.0)),
+            ((2.0, 2.0, 0.0), (-1.0, 0.0, 0.0)),
+        ];
+        for ((ox, oy, oz), (dx, dy, dz)) in cases {
+            let ray = Ray::set_ray(Tuple::set_vector(dx, dy, dz), Tuple::set_point(ox, oy, oz));
+            assert_eq!(cube.local_intersect(&ray).len(), 0);
+        }
+    }
+
+    #[test]
+    fn test_cube_normal() {
+        let cube = Cube::set_cube();
+        let cases = [
+            ((1.0, 0.5, -0.8), (1.0, 0.0, 0.0)),
+            ((-1.0, -0.2, 0.9), (-1.0, 0.0, 0.0)),
+            ((-0.4, 1.0, -0.1), (0.0, 1.0, 0.0)),
+            ((0.3, -1.0, -0.7), (0.0, -1.0, 0.0)),
+            ((-0.6, 0.3, 1.0), (0.0, 0.0, 1.0)),
+            ((0.4, 0.4, -1.0), (0.0, 0.0, -1.0)),
+            ((1.0, 1.0, 1.0), (1.0, 0.0, 0.0)),
+            ((-1.0, -1.0, -1.0), (-1.0, 0.0, 0.0)),
+        ];
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            let normal = cube.local_normal_at(Tuple::set_point(px, py, pz));
+            assert_eq!(normal, Tuple::set_vector(nx, ny, nz));
+        }
+    }
+
+    #[test]
+    fn test_transformed_cube_is_a_box() {
+        let mut cube = Cube::set_cube();
+        cube.set_transform(translation(0.0, 1.0, 0.0) * scaling(3.0, 1.0, 0.5));
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(2.5, 1.5, -5.0),
+        );
+        let xs = cube.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].t, 4.5);
+        assert_eq!(xs[1].t, 5.5);
+
+        let normal = cube.normal_at(Tuple::set_point(3.0, 1.2, 0.1));
+        assert_eq!(normal, Tuple::set_vector(1.0, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,239 @@
+#![allow(dead_code)]
+use crate::bounds::BoundingBox;
+use crate::intersection::Intersection;
+use crate::intersection::EPSILON;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+//radius 1 cylinder around the y axis, cut off at minimum and maximum
+#[derive(Debug, Clone)]
+pub struct Cylinder {
+    transform: Matrix,
+    inverse: Matrix,
+    pub material: Material,
+    pub minimum: f64,
+    pub maximum: f64,
+    //whether the cut off ends are capped
+    pub closed: bool,
+}
+
+impl Cylinder {
+    pub fn set_cylinder() -> Cylinder {
+        Cylinder {
+            transform: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+            material: Material::default_material(),
+            minimum: f64::NEG_INFINITY,
+            maximum: f64::INFINITY,
+            closed: false,
+        }
+    }
+
+    pub fn set_truncated(minimum: f64, maximum: f64, closed: bool) -> Cylinder {
+        let mut cylinder = Cylinder::set_cylinder();
+        cylinder.minimum = minimum;
+        cylinder.maximum = maximum;
+        cylinder.closed = closed;
+        cylinder
+    }
+
+    fn intersect_caps<'a>(&'a self, ray: &Ray, xs: &mut Vec<Intersection<'a>>) {
+        if !self.closed || ray.direction.y().abs() < EPSILON {
+            return;
+        }
+        for cap in [self.minimum, self.maximum] {
+            let t = (cap - ray.origin.y()) / ray.direction.y();
+            if check_cap(ray, t, 1.0) {
+                xs.push(Intersection::new(t, self));
+            }
+        }
+    }
+}
+
+//true when the ray at t is within radius of the y axis
+pub fn check_cap(ray: &Ray, t: f64, radius: f64) -> bool {
+    let x = ray.origin.x() + t * ray.direction.x();
+    let z = ray.origin.z() + t * ray.direction.z();
+    (x * x + z * z) <= radius * radius + EPSILON
+}
+
+impl Shape for Cylinder {
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let mut xs = Vec::new();
+        let (dx, dz) = (ray.direction.x(), ray.direction.z());
+        let (ox, oz) = (ray.origin.x(), ray.origin.z());
+
+        let a = dx * dx + dz * dz;
+        //a ray parallel to the y axis can only hit the caps
+        if a.abs() >= EPSILON {
+            let b = 2.0 * ox * dx + 2.0 * oz * dz;
+            let c = ox * ox + oz * oz - 1.0;
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant < 0.0 {
+                return xs;
+            }
+
+            let mut t0 = (-b - discriminant.sqrt()) / (2.0 * a);
+            let mut t1 = (-b + discriminant.sqrt()) / (2.0 * a);
+            if t0 > t1 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+
+            for t in [t0, t1] {
+                let y = ray.origin.y() + t * ray.direction.y();
+                if self.minimum < y && y < self.maximum {
+                    xs.push(Intersection::new(t, self));
+                }
+            }
+        }
+
+        self.intersect_caps(ray, &mut xs);
+        xs
+    }
+
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let distance = point.x() * point.x() + point.z() * point.z();
+        if distance < 1.0 && point.y() >= self.maximum - EPSILON {
+            Tuple::set_vector(0.0, 1.0, 0.0)
+        } else if distance < 1.0 && point.y() <= self.minimum + EPSILON {
+            Tuple::set_vector(0.0, -1.0, 0.0)
+        } else {
+            Tuple::set_vector(point.x(), 0.0, point.z())
+        }
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Tuple::set_point(-1.0, self.minimum, -1.0),
+            Tuple::set_point(1.0, self.maximum, 1.0),
+        )
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn inverse(&self) -> &Matrix {
+        &self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.inverse = transform.invert();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cast(cylinder: &Cylinder, origin: (f64, f64, f64), direction: (f64, f64, f64)) -> Vec<f64> {
+        let ray = Ray::set_ray(
+            Tuple::set_vector(direction.0, direction.1, direction.2).normalize(),
+            Tuple::set_point(origin.0, origin.1, origin.2),
+        );
+        cylinder.local_intersect(&ray).iter().map(|i| i.t).collect()
+    }
+
+    #[test]
+    fn test_ray_misses_cylinder() {
+        let cylinder = Cylinder::set_cylinder();
+        assert_eq!(cast(&cylinder, (1.0, 0.0, 0.0), (0.0, 1.0, 0.0)).len(), 0);
+        assert_eq!(cast(&cylinder, (0.0, 0.0, 0.0), (0.0, 1.0, 0.0)).len(), 0);
+        assert_eq!(cast(&cylinder, (0.0, 0.0, -5.0), (1.0, 1.0, 1.0)).len(), 0);
+    }
+
+    #[test]
+    fn test_ray_strikes_cylinder() {
+        let cylinder = Cylinder::set_cylinder();
+        let cases = [
+            ((1.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+            ((0.5, 0.0, -5.0), (0.1, 1.0, 1.0), 6.80798, 7.08872),
+        ];
+        for (origin, direction, t0, t1) in cases {
+            let xs = cast(&cylinder, origin, direction);
+            assert_eq!(xs.len(), 2);
+            assert!((xs[0] - t0).abs() < 0.0001);
+            assert!((xs[1] - t1).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_cylinder_normal() {
+        let cylinder = Cylinder::set_cylinder();
+        let cases = [
+            ((1.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((0.0, 5.0, -1.0), (0.0, 0.0, -1.0)),
+            ((0.0, -2.0, 1.0), (0.0, 0.0, 1.0)),
+            ((-1.0, 1.0, 0.0), (-1.0, 0.0, 0.0)),
+        ];
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            let normal = cylinder.local_normal_at(Tuple::set_point(px, py, pz));
+            assert_eq!(normal, Tuple::set_vector(nx, ny, nz));
+        }
+    }
+
+    #[test]
+    fn test_cylinder_default_bounds() {
+        let cylinder = Cylinder::set_cylinder();
+        assert_eq!(cylinder.minimum, f64::NEG_INFINITY);
+        assert_eq!(cylinder.maximum, f64::INFINITY);
+        assert!(!cylinder.closed);
+    }
+
+    #[test]
+    fn test_truncated_cylinder() {
+        let cylinder = Cylinder::set_truncated(1.0, 2.0, false);
+        let cases = [
+            ((0.0, 1.5, 0.0), (0.1, 1.0, 0.0), 0),
+            ((0.0, 3.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 2.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 1.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 1.5, -2.0), (0.0, 0.0, 1.0), 2),
+        ];
+        for (origin, direction, count) in cases {
+            assert_eq!(cast(&cylinder, origin, direction).len(), count);
+        }
+    }
+
+    #[test]
+    fn test_closed_cylinder_caps() {
+        let cylinder = Cylinder::set_truncated(1.0, 2.0, true);
+        let cases = [
+            ((0.0, 3.0, 0.0), (0.0, -1.0, 0.0)),
+            ((0.0, 3.0, -2.0), (0.0, -1.0, 2.0)),
+            ((0.0, 4.0, -2.0), (0.0, -1.0, 1.0)),
+            ((0.0, 0.0, -2.0), (0.0, 1.0, 2.0)),
+            ((0.0, -1.0, -2.0), (0.0, 1.0, 1.0)),
+        ];
+        for (origin, direction) in cases {
+            assert_eq!(cast(&cylinder, origin, direction).len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_closed_cylinder_cap_normals() {
+        let cylinder = Cylinder::set_truncated(1.0, 2.0, true);
+        let cases = [
+            ((0.0, 1.0, 0.0), (0.0, -1.0, 0.0)),
+            ((0.5, 1.0, 0.0), (0.0, -1.0, 0.0)),
+            ((0.0, 1.0, 0.5), (0.0, -1.0, 0.0)),
+            ((0.0, 2.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.5, 2.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 2.0, 0.5), (0.0, 1.0, 0.0)),
+        ];
+        for ((px, py, pz), (nx, ny, nz)) in cases {
+            let normal = cylinder.local_normal_at(Tuple::set_point(px, py, pz));
+            assert_eq!(normal, Tuple::set_vector(nx, ny, nz));
+        }
+    }
+}
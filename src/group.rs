@@ -0,0 +1,292 @@
+#![allow(dead_code)]
+use crate::bounds::BoundingBox;
+use crate::bvh::Bvh;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::shape::nearest_child;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+use std::sync::OnceLock;
+
+//a node in the scene graph, children can be any shape including other groups.
+//the group's transform is baked into every child so a child's own transform and
+//inverse always cover the whole parent chain, and normal_at works without parent links.
+//that leaves the children in world space, not in the group's local space
+#[derive(Debug)]
+pub struct Group {
+    transform: Matrix,
+    inverse: Matrix,
+    children: Vec<Box<dyn Shape>>,
+    material: Material,
+    //built on the first intersection, dropped whenever a child moves
+    bvh: OnceLock<Bvh>,
+}
+
+impl Group {
+    pub fn set_group() -> Group {
+        Group {
+            transform: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+            children: Vec::new(),
+            material: Material::default_material(),
+            bvh: OnceLock::new(),
+        }
+    }
+
+    //the child's transform is taken as relative to this group
+    pub fn add_child(&mut self, mut child: Box<dyn Shape>) {
+        child.set_transform(&self.transform * child.transform());
+        self.children.push(child);
+        self.bvh = OnceLock::new();
+    }
+
+    pub fn children(&self) -> &[Box<dyn Shape>] {
+        &self.children
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    //ray is in world space, where the children are
+    fn intersect_children(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let bvh = self.bvh.get_or_init(|| Bvh::build(&self.children));
+        bvh.intersect(&self.children, ray)
+    }
+}
+
+impl Shape for Group {
+    //the children are in world space, so the local ray is taken back out to meet them
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        self.intersect_children(&ray.transform(&self.transform))
+    }
+
+    //the point goes out to world space for the children and the normal comes back in.
+    //the transpose undoes the inverse transpose normal_at puts on it
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let normal = self.normal_at(&self.transform * &point);
+        let local = &self.transform.transpose() * &normal;
+        Tuple::set_vector(local.x(), local.y(), local.z())
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn inverse(&self) -> &Matrix {
+        &self.inverse
+    }
+
+    //swap the old transform for the new one in every child, nested groups recurse
+    fn set_transform(&mut self, transform: Matrix) {
+        let change = &transform * &self.inverse;
+        for child in self.children.iter_mut() {
+            let baked = &change * child.transform();
+            child.set_transform(baked);
+        }
+        self.inverse = transform.invert();
+        self.transform = transform;
+        self.bvh = OnceLock::new();
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        self.bounds().transform(&self.inverse)
+    }
+
+    //children are already in world space so their bounds are merged as they are
+    fn bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::empty();
+        for child in &self.children {
+            bounds.merge(&child.bounds());
+        }
+        bounds
+    }
+
+    fn includes(&self, other: &dyn Shape) -> bool {
+        self.children.iter().any(|child| child.includes(other))
+    }
+
+    //skips the round trip through local space
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        self.intersect_children(ray)
+    }
+
+    //an empty group has no surface at all, up is as good as any
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        match nearest_child(self.children.iter().map(|child| child.as_ref()), &point) {
+            Some(child) => child.normal_at(point),
+            None => Tuple::set_vector(0.0, 1.0, 0.0),
+        }
+    }
+
+    //hits always point at the primitive, so that's asked directly
+    fn normal_at_hit(&self, point: Tuple, hit: &Intersection) -> Tuple {
+        if self.includes(hit.object) {
+            hit.object.normal_at_hit(point, hit)
+        } else {
+            self.normal_at(point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intersection::same_object;
+    use crate::sphere::Sphere;
+    use crate::transform::rotate_y;
+    use crate::transform::scaling;
+    use crate::transform::translation;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_new_group() {
+        let group = Group::set_group();
+        assert_eq!(group.transform(), &Matrix::identity(4, 4));
+        assert!(group.is_empty());
+    }
+
+    #[test]
+    fn test_intersect_empty_group() {
+        let group = Group::set_group();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, 0.0),
+        );
+        assert!(group.intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn test_intersect_group_children() {
+        let mut group = Group::set_group();
+        let s1 = Sphere::unit();
+        let mut s2 = Sphere::unit();
+        s2.set_transform(translation(0.0, 0.0, -3.0));
+        let mut s3 = Sphere::unit();
+        s3.set_transform(translation(5.0, 0.0, 0.0));
+        group.add_child(Box::new(s1));
+        group.add_child(Box::new(s2));
+        group.add_child(Box::new(s3));
+
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let xs = group.intersect(&ray);
+        let children = group.children();
+        assert_eq!(xs.len(), 4);
+        assert!(same_object(xs[0].object, children[1].as_ref()));
+        assert!(same_object(xs[1].object, children[1].as_ref()));
+        assert!(same_object(xs[2].object, children[0].as_ref()));
+        assert!(same_object(xs[3].object, children[0].as_ref()));
+    }
+
+    #[test]
+    fn test_intersect_transformed_group() {
+        let mut group = Group::set_group();
+        group.set_transform(scaling(2.0, 2.0, 2.0));
+        let mut sphere = Sphere::unit();
+        sphere.set_transform(translation(5.0, 0.0, 0.0));
+        group.add_child(Box::new(sphere));
+
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(10.0, 0.0, -10.0),
+        );
+        assert_eq!(group.intersect(&ray).len(), 2);
+    }
+
+    #[test]
+    fn test_transform_set_after_children_are_added() {
+        let mut group = Group::set_group();
+        let mut sphere = Sphere::unit();
+        sphere.set_transform(translation(5.0, 0.0, 0.0));
+        group.add_child(Box::new(sphere));
+        group.set_transform(scaling(2.0, 2.0, 2.0));
+        group.set_transform(scaling(2.0, 2.0, 2.0));
+
+        //replacing the group transform must not stack the old one
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(10.0, 0.0, -10.0),
+        );
+        let xs = group.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].t, 8.0);
+        assert_eq!(xs[1].t, 12.0);
+    }
+
+    #[test]
+    fn test_group_bounds_cover_children() {
+        let mut group = Group::set_group();
+        group.set_transform(translation(0.0, 1.0, 0.0));
+        let mut sphere = Sphere::unit();
+        sphere.set_transform(translation(2.0, 5.0, -3.0));
+        group.add_child(Box::new(sphere));
+        let mut other = Sphere::unit();
+        other.set_transform(scaling(0.5, 0.5, 0.5));
+        group.add_child(Box::new(other));
+
+        let bounds = group.bounds();
+        assert_eq!(bounds.min, Tuple::set_point(-0.5, 0.5, -4.0));
+        assert_eq!(bounds.max, Tuple::set_point(3.0, 7.0, 0.5));
+        assert_eq!(group.local_bounds().min, Tuple::set_point(-0.5, -0.5, -4.0));
+    }
+
+    #[test]
+    fn test_normal_on_child_of_nested_groups() {
+        let mut g1 = Group::set_group();
+        g1.set_transform(rotate_y(FRAC_PI_2));
+        let mut g2 = Group::set_group();
+        g2.set_transform(scaling(1.0, 2.0, 3.0));
+        let mut sphere = Sphere::unit();
+        sphere.set_transform(translation(5.0, 0.0, 0.0));
+        g2.add_child(Box::new(sphere));
+        g1.add_child(Box::new(g2));
+
+        //the sphere ends up centred on (0, 0, -5) in world space
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, -1.0),
+            Tuple::set_point(0.0, 0.0, 0.0),
+        );
+        let xs = g1.intersect(&ray);
+        assert_eq!(xs.len(), 2);
+        let normal = xs[0]
+            .object
+            .normal_at(Tuple::set_point(1.7321, 1.1547, -5.5774));
+        assert_eq!(normal, Tuple::set_vector(0.2857, 0.4286, -0.8571));
+    }
+
+    #[test]
+    fn test_group_normal_comes_from_child() {
+        let mut g1 = Group::set_group();
+        g1.set_transform(rotate_y(FRAC_PI_2));
+        let mut g2 = Group::set_group();
+        g2.set_transform(scaling(1.0, 2.0, 3.0));
+        let mut sphere = Sphere::unit();
+        sphere.set_transform(translation(5.0, 0.0, 0.0));
+        let mut far = Sphere::unit();
+        far.set_transform(translation(-20.0, 0.0, 0.0));
+        g2.add_child(Box::new(sphere));
+        g1.add_child(Box::new(g2));
+        g1.add_child(Box::new(far));
+
+        //the sphere two groups down answers for both groups, in world and local space
+        let point = Tuple::set_point(1.7321, 1.1547, -5.5774);
+        let expected = Tuple::set_vector(0.2857, 0.4286, -0.8571);
+        assert_eq!(g1.normal_at(point.clone()), expected);
+        assert_eq!(g1.children()[0].normal_at(point.clone()), expected);
+        let local = g1.local_normal_at(g1.inverse() * &point).normalize();
+        assert_eq!(&g1.inverse().transpose() * &local, expected);
+        assert_eq!(
+            Group::set_group().normal_at(point),
+            Tuple::set_vector(0.0, 1.0, 0.0)
+        );
+    }
+}
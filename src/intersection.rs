@@ -0,0 +1,372 @@
+#![allow(dead_code)]
+use crate::ray::reflect;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+//how far to nudge points off a surface so they don't hit it again
+pub const EPSILON: f64 = 0.0001;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Intersection<'a> {
+    pub t: f64,
+    pub object: &'a dyn Shape,
+    //where on a triangle the hit landed, zero for every other shape
+    pub u: f64,
+    pub v: f64,
+}
+
+impl<'a> Intersection<'a> {
+    pub fn new(t: f64, object: &'a dyn Shape) -> Intersection<'a> {
+        Intersection::with_uv(t, object, 0.0, 0.0)
+    }
+
+    pub fn with_uv(t: f64, object: &'a dyn Shape, u: f64, v: f64) -> Intersection<'a> {
+        Intersection { t, object, u, v }
+    }
+
+    //xs is every intersection along the ray, needed to know which materials the hit sits between
+    pub fn prepare_computations(&self, ray: &Ray, xs: &[Intersection<'a>]) -> Computations<'a> {
+        let point = ray.calculate_position(self.t);
+        let eye_vector = -ray.direction.clone();
+        let mut normal_vector = self.object.normal_at_hit(point.clone(), self);
+        let inside = Tuple::dot(normal_vector.clone(), eye_vector.clone()) < 0.0;
+        if inside {
+            normal_vector = -normal_vector;
+        }
+        let over_point = point.clone() + normal_vector.clone() * EPSILON;
+        let under_point = point.clone() - normal_vector.clone() * EPSILON;
+        let reflect_vector = reflect(ray.direction.clone(), normal_vector.clone());
+        let (n1, n2) = self.refractive_indices(xs);
+        Computations {
+            t: self.t,
+            object: self.object,
+            point,
+            over_point,
+            under_point,
+            eye_vector,
+            normal_vector,
+            reflect_vector,
+            inside,
+            n1,
+            n2,
+        }
+    }
+
+    //walk the sorted intersections tracking which objects the ray is inside of,
+    //n1 is the index being left and n2 the one being entered at this hit
+    fn refractive_indices(&self, xs: &[Intersection<'a>]) -> (f64, f64) {
+        let mut containers: Vec<&dyn Shape> = Vec::new();
+        let mut n1 = 1.0;
+        let mut n2 = 1.0;
+        for intersection in xs {
+            let is_hit = intersection.t == self.t && same_object(intersection.object, self.object);
+            if is_hit {
+                n1 = match containers.last() {
+                    Some(object) => object.material().refractive_index,
+                    None => 1.0,
+                };
+            }
+
+            match containers
+                .iter()
+                .position(|object| same_object(*object, intersection.object))
+            {
+                Some(index) => {
+                    containers.remove(index);
+                }
+                None => containers.push(intersection.object),
+            }
+
+            if is_hit {
+                n2 = match containers.last() {
+                    Some(object) => object.material().refractive_index,
+                    None => 1.0,
+                };
+                break;
+            }
+        }
+        (n1, n2)
+    }
+}
+
+//two trait objects are the same shape when they point at the same data
+pub fn same_object(a: &dyn Shape, b: &dyn Shape) -> bool {
+    std::ptr::addr_eq(a as *const dyn Shape, b as *const dyn Shape)
+}
+
+//everything about a hit that shading needs, computed once up front
+#[derive(Debug, Clone)]
+pub struct Computations<'a> {
+    pub t: f64,
+    pub object: &'a dyn Shape,
+    pub point: Tuple,
+    //point lifted slightly along the normal, used to avoid shadow acne
+    pub over_point: Tuple,
+    //point pushed just below the surface, where refracted rays start
+    pub under_point: Tuple,
+    pub eye_vector: Tuple,
+    pub normal_vector: Tuple,
+    pub reflect_vector: Tuple,
+    pub inside: bool,
+    pub n1: f64,
+    pub n2: f64,
+}
+
+impl Computations<'_> {
+    //schlick's approximation of the fresnel effect, the fraction of light reflected
+    pub fn schlick(&self) -> f64 {
+        let mut cos = Tuple::dot(self.eye_vector.clone(), self.normal_vector.clone());
+        if self.n1 > self.n2 {
+            let n = self.n1 / self.n2;
+            let sin2_t = n * n * (1.0 - cos * cos);
+            if sin2_t > 1.0 {
+                return 1.0;
+            }
+            cos = (1.0 - sin2_t).sqrt();
+        }
+        let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powi(2);
+        r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+    }
+}
+
+pub fn sort_intersections(xs: &mut [Intersection]) {
+    xs.sort_by(|a, b| a.t.total_cmp(&b.t));
+}
+
+//the hit is the lowest non-negative t, anything behind the ray is ignored
+pub fn hit<'a, 'b>(xs: &'b [Intersection<'a>]) -> Option<&'b Intersection<'a>> {
+    xs.iter()
+        .filter(|intersection| intersection.t >= 0.0)
+        .min_by(|a, b| a.t.total_cmp(&b.t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sphere::glass_sphere;
+    use crate::sphere::Sphere;
+    use crate::transform::scaling;
+    use crate::transform::translation;
+    use crate::tuple::Tuple;
+
+    #[test]
+    fn test_intersection_struct() {
+        let s = Sphere::unit();
+        let i = Intersection::new(3.5, &s);
+        assert_eq!(i.t, 3.5);
+        assert_eq!(i.object.material(), s.material());
+    }
+
+    #[test]
+    fn test_intersection_with_uv() {
+        let s = Sphere::unit();
+        let i = Intersection::with_uv(3.5, &s, 0.2, 0.4);
+        assert_eq!(i.t, 3.5);
+        assert_eq!(i.u, 0.2);
+        assert_eq!(i.v, 0.4);
+    }
+
+    #[test]
+    fn test_hit_all_positive() {
+        let s = Sphere::unit();
+        let xs = vec![Intersection::new(1.0, &s), Intersection::new(2.0, &s)];
+        assert_eq!(hit(&xs).unwrap().t, 1.0);
+    }
+
+    #[test]
+    fn test_hit_some_negative() {
+        let s = Sphere::unit();
+        let xs = vec![Intersection::new(-1.0, &s), Intersection::new(1.0, &s)];
+        assert_eq!(hit(&xs).unwrap().t, 1.0);
+    }
+
+    #[test]
+    fn test_hit_all_negative() {
+        let s = Sphere::unit();
+        let xs = vec![Intersection::new(-2.0, &s), Intersection::new(-1.0, &s)];
+        assert!(hit(&xs).is_none());
+    }
+
+    #[test]
+    fn test_hit_lowest_non_negative() {
+        let s = Sphere::unit();
+        let xs = vec![
+            Intersection::new(5.0, &s),
+            Intersection::new(7.0, &s),
+            Intersection::new(-3.0, &s),
+            Intersection::new(2.0, &s),
+        ];
+        assert_eq!(hit(&xs).unwrap().t, 2.0);
+    }
+
+    #[test]
+    fn test_sort_intersections() {
+        let s = Sphere::unit();
+        let mut xs = vec![
+            Intersection::new(5.0, &s),
+            Intersection::new(-3.0, &s),
+            Intersection::new(2.0, &s),
+        ];
+        sort_intersections(&mut xs);
+        let ts: Vec<f64> = xs.iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![-3.0, 2.0, 5.0]);
+    }
+
+    #[test]
+    fn test_prepare_computations_outside() {
+        let s = Sphere::unit();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let i = Intersection::new(4.0, &s);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert_eq!(comps.t, 4.0);
+        assert_eq!(comps.point, Tuple::set_point(0.0, 0.0, -1.0));
+        assert_eq!(comps.eye_vector, Tuple::set_vector(0.0, 0.0, -1.0));
+        assert_eq!(comps.normal_vector, Tuple::set_vector(0.0, 0.0, -1.0));
+        assert!(!comps.inside);
+    }
+
+    #[test]
+    fn test_prepare_computations_inside() {
+        let s = Sphere::unit();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, 0.0),
+        );
+        let i = Intersection::new(1.0, &s);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert_eq!(comps.point, Tuple::set_point(0.0, 0.0, 1.0));
+        assert_eq!(comps.eye_vector, Tuple::set_vector(0.0, 0.0, -1.0));
+        assert_eq!(comps.normal_vector, Tuple::set_vector(0.0, 0.0, -1.0));
+        assert!(comps.inside);
+    }
+
+    #[test]
+    fn test_prepare_computations_over_point() {
+        let mut s = Sphere::unit();
+        s.set_transform(translation(0.0, 0.0, 1.0));
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let i = Intersection::new(5.0, &s);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert!(comps.over_point.z() < -EPSILON / 2.0);
+        assert!(comps.point.z() > comps.over_point.z());
+    }
+
+    #[test]
+    fn test_prepare_computations_reflect_vector() {
+        let s = Sphere::unit();
+        let root = 2.0_f64.sqrt() / 2.0;
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, -root, root),
+            Tuple::set_point(0.0, 1.0, -2.0),
+        );
+        let i = Intersection::new(2.0_f64.sqrt(), &s);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert_eq!(comps.point, Tuple::set_point(0.0, 0.0, -1.0));
+        assert_eq!(comps.reflect_vector, Tuple::set_vector(0.0, -root, -root));
+    }
+
+    #[test]
+    fn test_same_object() {
+        let a = Sphere::unit();
+        let b = Sphere::unit();
+        assert!(same_object(&a, &a));
+        assert!(!same_object(&a, &b));
+    }
+
+    #[test]
+    fn test_n1_n2_at_various_intersections() {
+        let mut a = glass_sphere();
+        a.set_transform(scaling(2.0, 2.0, 2.0));
+        a.material.refractive_index = 1.5;
+        let mut b = glass_sphere();
+        b.set_transform(translation(0.0, 0.0, -0.25));
+        b.material.refractive_index = 2.0;
+        let mut c = glass_sphere();
+        c.set_transform(translation(0.0, 0.0, 0.25));
+        c.material.refractive_index = 2.5;
+
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -4.0),
+        );
+        let xs = vec![
+            Intersection::new(2.0, &a),
+            Intersection::new(2.75, &b),
+            Intersection::new(3.25, &c),
+            Intersection::new(4.75, &b),
+            Intersection::new(5.25, &c),
+            Intersection::new(6.0, &a),
+        ];
+        let expected = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+        for (index, (n1, n2)) in expected.iter().enumerate() {
+            let comps = xs[index].prepare_computations(&ray, &xs);
+            assert_eq!(comps.n1, *n1);
+            assert_eq!(comps.n2, *n2);
+        }
+    }
+
+    #[test]
+    fn test_prepare_computations_under_point() {
+        let mut s = glass_sphere();
+        s.set_transform(translation(0.0, 0.0, 1.0));
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let i = Intersection::new(5.0, &s);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert!(comps.under_point.z() > EPSILON / 2.0);
+        assert!(comps.point.z() < comps.under_point.z());
+    }
+
+    #[test]
+    fn test_schlick_total_internal_reflection() {
+        let s = glass_sphere();
+        let root = 2.0_f64.sqrt() / 2.0;
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_point(0.0, 0.0, root),
+        );
+        let xs = vec![Intersection::new(-root, &s), Intersection::new(root, &s)];
+        let comps = xs[1].prepare_computations(&ray, &xs);
+        assert_eq!(comps.schlick(), 1.0);
+    }
+
+    #[test]
+    fn test_schlick_perpendicular() {
+        let s = glass_sphere();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_point(0.0, 0.0, 0.0),
+        );
+        let xs = vec![Intersection::new(-1.0, &s), Intersection::new(1.0, &s)];
+        let comps = xs[1].prepare_computations(&ray, &xs);
+        assert!((comps.schlick() - 0.04).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_schlick_small_angle() {
+        let s = glass_sphere();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.99, -2.0),
+        );
+        let xs = vec![Intersection::new(1.8589, &s)];
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        assert!((comps.schlick() - 0.48873).abs() < 0.0001);
+    }
+}
@@ -2,12 +2,13 @@ use crate::color::color;
 use crate::color::Color;
 use crate::material::Material;
 use crate::ray::reflect;
+use crate::shape::Shape;
 use crate::tuple::Tuple;
 
 #[derive(Debug, Clone)]
 pub struct PointLight {
-    intensity: Color,
-    position: Tuple,
+    pub intensity: Color,
+    pub position: Tuple,
 }
 
 impl PointLight {
@@ -19,19 +20,30 @@ impl PointLight {
     }
 }
 
+//object is only needed to map point into pattern space
 pub fn lighting(
-    m: Material,
+    m: &Material,
+    object: &dyn Shape,
     light: PointLight,
     point: Tuple,
     eye_vector: Tuple,
     normal_vector: Tuple,
+    in_shadow: bool,
 ) -> Color {
     let diffuse: Color;
     let specular: Color;
-    let effective_color = m.color * light.intensity.clone();
+    let surface_color = match &m.pattern {
+        Some(pattern) => pattern.pattern_at_shape(object, &point),
+        None => m.color.clone(),
+    };
+    let effective_color = surface_color * light.intensity.clone();
 
     let light_vector = (light.position - point).normalize();
     let ambient = effective_color.clone() * m.ambient;
+    //only ambient light reaches a point the light can't see
+    if in_shadow {
+        return ambient;
+    }
     let light_dot_normal = Tuple::dot(light_vector.clone(), normal_vector.clone());
     if light_dot_normal < 0.0 {
         diffuse = Color::create_black();
@@ -57,6 +69,8 @@ pub fn lighting(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pattern::Pattern;
+    use crate::sphere::Sphere;
 
     #[test]
     fn test_light_struct() {
@@ -78,7 +92,15 @@ mod tests {
         let light_intensity = color(1.0, 1.0, 1.0);
         let light_position = Tuple::set_point(0.0, 0.0, -10.0);
         let point_light = PointLight::set_point_light(light_intensity, light_position);
-        let actual = lighting(m, point_light, position, eye_vector, normal_vector);
+        let actual = lighting(
+            &m,
+            &Sphere::unit(),
+            point_light,
+            position,
+            eye_vector,
+            normal_vector,
+            false,
+        );
 
         let expected = color(1.9, 1.9, 1.9);
 
@@ -96,7 +118,15 @@ mod tests {
         let light_intensity = color(1.0, 1.0, 1.0);
         let light_position = Tuple::set_point(0.0, 0.0, -10.0);
         let point_light = PointLight::set_point_light(light_intensity, light_position);
-        let actual = lighting(m, point_light, position, eye_vector, normal_vector);
+        let actual = lighting(
+            &m,
+            &Sphere::unit(),
+            point_light,
+            position,
+            eye_vector,
+            normal_vector,
+            false,
+        );
 
         let expected = color(1.0, 1.0, 1.0);
 
@@ -114,7 +144,15 @@ mod tests {
         let light_intensity = color(1.0, 1.0, 1.0);
         let light_position = Tuple::set_point(0.0, 10.0, -10.0);
         let point_light = PointLight::set_point_light(light_intensity, light_position);
-        let actual = lighting(m, point_light, position, eye_vector, normal_vector);
+        let actual = lighting(
+            &m,
+            &Sphere::unit(),
+            point_light,
+            position,
+            eye_vector,
+            normal_vector,
+            false,
+        );
 
         let expected = color(0.7364, 0.7364, 0.7364);
 
@@ -132,7 +170,15 @@ mod tests {
         let light_intensity = color(1.0, 1.0, 1.0);
         let light_position = Tuple::set_point(0.0, 10.0, -10.0);
         let point_light = PointLight::set_point_light(light_intensity, light_position);
-        let actual = lighting(m, point_light, position, eye_vector, normal_vector);
+        let actual = lighting(
+            &m,
+            &Sphere::unit(),
+            point_light,
+            position,
+            eye_vector,
+            normal_vector,
+            false,
+        );
 
         let expected = color(1.6364, 1.6364, 1.6364);
 
@@ -150,10 +196,78 @@ mod tests {
         let light_intensity = color(1.0, 1.0, 1.0);
         let light_position = Tuple::set_point(0.0, 0.0, 10.0);
         let point_light = PointLight::set_point_light(light_intensity, light_position);
-        let actual = lighting(m, point_light, position, eye_vector, normal_vector);
+        let actual = lighting(
+            &m,
+            &Sphere::unit(),
+            point_light,
+            position,
+            eye_vector,
+            normal_vector,
+            false,
+        );
+
+        let expected = color(0.1, 0.1, 0.1);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_lighting_in_shadow() {
+        let m = Material::default_material();
+        let position = Tuple::set_point(0.0, 0.0, 0.0);
+
+        let eye_vector = Tuple::set_vector(0.0, 0.0, -1.0);
+        let normal_vector = Tuple::set_vector(0.0, 0.0, -1.0);
+
+        let light_intensity = color(1.0, 1.0, 1.0);
+        let light_position = Tuple::set_point(0.0, 0.0, -10.0);
+        let point_light = PointLight::set_point_light(light_intensity, light_position);
+        let actual = lighting(
+            &m,
+            &Sphere::unit(),
+            point_light,
+            position,
+            eye_vector,
+            normal_vector,
+            true,
+        );
 
         let expected = color(0.1, 0.1, 0.1);
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_lighting_with_pattern() {
+        let mut m = Material::default_material();
+        m.pattern = Some(Pattern::stripe(color(1.0, 1.0, 1.0), color(0.0, 0.0, 0.0)));
+        m.ambient = 1.0;
+        m.diffuse = 0.0;
+        m.specular = 0.0;
+        let eye_vector = Tuple::set_vector(0.0, 0.0, -1.0);
+        let normal_vector = Tuple::set_vector(0.0, 0.0, -1.0);
+        let light =
+            PointLight::set_point_light(color(1.0, 1.0, 1.0), Tuple::set_point(0.0, 0.0, -10.0));
+        let object = Sphere::unit();
+        let c1 = lighting(
+            &m,
+            &object,
+            light.clone(),
+            Tuple::set_point(0.9, 0.0, 0.0),
+            eye_vector.clone(),
+            normal_vector.clone(),
+            false,
+        );
+        let c2 = lighting(
+            &m,
+            &object,
+            light,
+            Tuple::set_point(1.1, 0.0, 0.0),
+            eye_vector,
+            normal_vector,
+            false,
+        );
+        assert_eq!(c1, color(1.0, 1.0, 1.0));
+        assert_eq!(c2, color(0.0, 0.0, 0.0));
+    }
 }
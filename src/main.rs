@@ -1,56 +1,110 @@
+mod bounds;
+mod bvh;
+mod camera;
 mod canvas;
 mod color;
+mod cone;
+mod csg;
+mod cube;
+mod cylinder;
+mod group;
+mod intersection;
 mod light;
 mod material;
 mod matrix;
+mod mtl;
+mod noise;
+mod obj;
+mod pattern;
+mod plane;
 mod ray;
+mod shape;
+mod sphere;
+mod texture;
 mod transform;
+mod triangle;
 mod tuple;
-use crate::canvas::Canvas;
+mod uv;
+mod world;
+use crate::camera::render;
+use crate::camera::Camera;
 use crate::color::color;
-use crate::ray::Ray;
-use crate::ray::Sphere;
-use crate::tuple::Tuple;
 use crate::light::PointLight;
- use crate::light::lighting;
+use crate::plane::Plane;
+use crate::shape::Shape;
+use crate::sphere::Sphere;
+use crate::transform::view_transform;
+use crate::transform::Transform;
+use crate::tuple::Tuple;
+use crate::world::World;
+use std::f64::consts::PI;
 
 fn main() {
-    let ray_origin = Tuple::set_point(0.0, 0.0, -5.0);
-    let wall = 10.0;
-    //not entirely sure what this wall is suppose to represent
-    let canvas_size = 100;
-    let mut canvas = Canvas::zero(canvas_size, canvas_size);
-    
-    let light_position = Tuple::set_point(-10.0, 10.0, 8.0);
-    let light_color = color(1.0, 1.0, 1.0);
-    let light = PointLight::set_point_light(light_color, light_position);
-    
-    let sphere_origin = Tuple::set_point(0.0, 0.0, 0.0);
-    let sphere_radius = 1.2;
-    //as the sphere gets closer to 1.4 the sphere gets too large to render
-    let mut test_sphere = Sphere::set_sphere(sphere_origin, sphere_radius);
-    test_sphere.material.color = color(1.0, 2.0, 0.0);
-    
-    for y in 0..(canvas_size - 1) {
-        let world_y = (-1.0 * (y as f64)) + (canvas_size as f64) / 2.0;
-        for x in 0..(canvas_size - 1) {
-            let world_x = (x as f64) - (canvas_size as f64) / 2.0;
-            let position = Tuple::set_point(world_x, world_y, wall);
-            let new_vector = position - ray_origin.clone();
-            let r = Ray::set_ray(ray_origin.clone(), new_vector.normalize());
-            let xs = Ray::sphere_intersect(&r, &test_sphere);
-            
-            if xs.len() > 0 {
-                let hit_position = r.calculate_position(xs[0]);
-                let normal = test_sphere.normal_at(hit_position.clone());
-                let eye_vector = -r.direction;
-                let current_color = lighting(test_sphere.material.clone(), light.clone(), hit_position.clone(), eye_vector.clone(), normal.clone());
-                canvas.write_pixel(x, y, current_color.clone());
-            }
-
-        }
-    }
-
-    let name = "../examples/chapter6.ppm";
-    canvas.canvas_to_ppm(name, false).expect("Could not write canvas to ppm")
+    let mut world = World::new();
+
+    let mut floor = Plane::set_plane();
+    floor.material.color = color(1.0, 0.9, 0.9);
+    floor.material.specular = 0.0;
+    floor.material.reflective = 0.2;
+
+    let mut back_wall = Plane::set_plane();
+    back_wall.set_transform(
+        Transform::identity()
+            .rotate_x(PI / 2.0)
+            .translate(0.0, 0.0, 5.0)
+            .into(),
+    );
+    back_wall.material.color = color(1.0, 0.9, 0.9);
+    back_wall.material.specular = 0.0;
+
+    let mut middle = Sphere::unit();
+    middle.set_transform(Transform::identity().translate(-0.5, 1.0, 0.5).into());
+    middle.material.color = color(0.1, 1.0, 0.5);
+    middle.material.diffuse = 0.7;
+    middle.material.specular = 0.3;
+
+    let mut right = Sphere::unit();
+    right.set_transform(
+        Transform::identity()
+            .scale(0.5, 0.5, 0.5)
+            .translate(1.5, 0.5, -0.5)
+            .into(),
+    );
+    right.material.color = color(0.5, 1.0, 0.1);
+    right.material.diffuse = 0.7;
+    right.material.specular = 0.3;
+
+    let mut left = Sphere::unit();
+    left.set_transform(
+        Transform::identity()
+            .scale(0.33, 0.33, 0.33)
+            .translate(-1.5, 0.33, -0.75)
+            .into(),
+    );
+    left.material.color = color(1.0, 0.8, 0.1);
+    left.material.diffuse = 0.7;
+    left.material.specular = 0.3;
+
+    world.add_object(Box::new(floor));
+    world.add_object(Box::new(back_wall));
+    world.add_object(Box::new(middle));
+    world.add_object(Box::new(right));
+    world.add_object(Box::new(left));
+    world.add_light(PointLight::set_point_light(
+        color(1.0, 1.0, 1.0),
+        Tuple::set_point(-10.0, 10.0, -10.0),
+    ));
+
+    let mut camera = Camera::new(100, 50, PI / 3.0);
+    camera.set_transform(view_transform(
+        Tuple::set_point(0.0, 1.5, -5.0),
+        Tuple::set_point(0.0, 1.0, 0.0),
+        Tuple::set_vector(0.0, 1.0, 0.0),
+    ));
+
+    let canvas = render(&camera, &world);
+    let name = "../examples/chapter9.ppm";
+    canvas
+        .canvas_to_ppm(name, false)
+        .expect("Could not write canvas to ppm")
 }
@@ -1,5 +1,6 @@
 use crate::color::color;
 use crate::color::Color;
+use crate::pattern::Pattern;
 use float_cmp::approx_eq;
 
 #[derive(Debug, Clone)]
@@ -9,6 +10,13 @@ pub struct Material {
     pub diffuse: f64,
     pub specular: f64,
     pub shininess: f64,
+    //0.0 is a matte surface, 1.0 a perfect mirror
+    pub reflective: f64,
+    pub transparency: f64,
+    //1.0 is a vacuum, glass is around 1.5
+    pub refractive_index: f64,
+    //when set the pattern is used instead of color
+    pub pattern: Option<Pattern>,
 }
 
 impl PartialEq for Material {
@@ -17,8 +25,26 @@ impl PartialEq for Material {
         let diffuse_comp = approx_eq!(f64, self.diffuse, other.diffuse, epsilon = 0.0001);
         let specular_comp = approx_eq!(f64, self.specular, other.specular, epsilon = 0.0001);
         let shininess_comp = approx_eq!(f64, self.shininess, other.shininess, epsilon = 0.0001);
+        let reflective_comp = approx_eq!(f64, self.reflective, other.reflective, epsilon = 0.0001);
+        let transparency_comp =
+            approx_eq!(f64, self.transparency, other.transparency, epsilon = 0.0001);
+        let refractive_comp = approx_eq!(
+            f64,
+            self.refractive_index,
+            other.refractive_index,
+            epsilon = 0.0001
+        );
         let color_comp = self.color == other.color;
-        return ambient_comp && diffuse_comp && specular_comp && shininess_comp && color_comp;
+        let pattern_comp = self.pattern == other.pattern;
+        ambient_comp
+            && diffuse_comp
+            && specular_comp
+            && shininess_comp
+            && reflective_comp
+            && transparency_comp
+            && refractive_comp
+            && color_comp
+            && pattern_comp
     }
 }
 
@@ -30,6 +56,10 @@ impl Material {
             diffuse: 0.9,
             specular: 0.9,
             shininess: 20.0,
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
+            pattern: None,
         }
     }
 }
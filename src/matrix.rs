@@ -1,7 +1,7 @@
 use crate::tuple::Tuple;
 use float_cmp::approx_eq;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Matrix {
     width: usize,
     height: usize,
@@ -171,6 +171,14 @@ fn calculate_element_sum(a: &Vec<f64>, b: &Vec<f64>) -> f64 {
 impl std::ops::Mul<Matrix> for Matrix {
     type Output = Matrix;
     fn mul(self, rhs: Matrix) -> Matrix {
+        &self * &rhs
+    }
+}
+
+//multiplying by reference leaves both matrices usable afterwards
+impl std::ops::Mul<&Matrix> for &Matrix {
+    type Output = Matrix;
+    fn mul(self, rhs: &Matrix) -> Matrix {
         if self.width != rhs.height {
             panic!("left element width doesn't equal right element")
         }
@@ -190,6 +198,13 @@ impl std::ops::Mul<Matrix> for Matrix {
 impl std::ops::Mul<Tuple> for Matrix {
     type Output = Tuple;
     fn mul(self, rhs: Tuple) -> Tuple {
+        &self * &rhs
+    }
+}
+
+impl std::ops::Mul<&Tuple> for &Matrix {
+    type Output = Tuple;
+    fn mul(self, rhs: &Tuple) -> Tuple {
         let mut output_tuple_vector = vec![0.0, 0.0, 0.0, 0.0];
         for y_index in 0..self.height {
             let matrix_row = self.get_row(y_index);
@@ -503,4 +518,23 @@ mod tests {
         let b = Matrix::set(&b_vec);
         assert_eq!(a.invert(), b);
     }
+
+    #[test]
+    fn test_matrix_multiplication_by_reference() {
+        let a_vec = vec![vec![-3.0, 5.0, 1.0], vec![1.0, 2.0, 1.0]];
+        let b_vec = vec![vec![3.0, 5.0], vec![2.0, 1.0], vec![1.0, -2.0]];
+        let c_vec = vec![vec![2.0, -12.0], vec![8.0, 5.0]];
+        let a = Matrix::set(&a_vec);
+        let b = Matrix::set(&b_vec);
+        let c = Matrix::set(&c_vec);
+        assert_eq!(&a * &b, c);
+        assert_eq!(a * b, c);
+    }
+
+    #[test]
+    fn test_matrix_multiplication_tuple_by_reference() {
+        let a = Matrix::identity(4, 4);
+        let b = Tuple::set_point(1.0, 2.0, 3.0);
+        assert_eq!(&a * &b, b);
+    }
 }
@@ -0,0 +1,231 @@
+#![allow(dead_code)]
+use crate::color::color;
+use crate::color::Color;
+use crate::material::Material;
+use crate::obj::parse_numbers;
+use crate::obj::ObjWarning;
+use crate::obj::ObjWarningKind;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+//materials from one or more .mtl files, keyed by their newmtl name
+#[derive(Debug, Default)]
+pub struct MtlLibrary {
+    pub materials: HashMap<String, Material>,
+    //texture maps aren't rendered yet, the path from map_Kd is only recorded
+    pub diffuse_maps: HashMap<String, String>,
+    pub warnings: Vec<ObjWarning>,
+}
+
+impl MtlLibrary {
+    pub fn new() -> MtlLibrary {
+        MtlLibrary::default()
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> std::io::Result<MtlLibrary> {
+        let source = fs::read_to_string(path)?;
+        Ok(parse_mtl(&source))
+    }
+
+    pub fn material(&self, name: &str) -> Option<&Material> {
+        self.materials.get(name)
+    }
+
+    //later libraries win when two define the same name
+    pub fn merge(&mut self, other: MtlLibrary) {
+        self.materials.extend(other.materials);
+        self.diffuse_maps.extend(other.diffuse_maps);
+        self.warnings.extend(other.warnings);
+    }
+}
+
+fn average(rgb: &Color) -> f64 {
+    (rgb.red + rgb.green + rgb.blue) / 3.0
+}
+
+//exporters write Ka as a color, usually white or black, not as a coefficient. it scales the
+//default ambient instead, and black is taken as unset so models don't lose all ambient light
+fn ambient_from(rgb: &Color) -> f64 {
+    let default = Material::default_material().ambient;
+    match average(rgb) {
+        brightness if brightness <= 0.0 => default,
+        brightness => default * brightness.min(1.0),
+    }
+}
+
+//our material scales a single color, so Ka and Ks only keep their brightness
+fn apply_statement(
+    material: &mut Material,
+    keyword: &str,
+    arguments: &[&str],
+) -> Option<Result<(), String>> {
+    let result = match keyword {
+        "Kd" => parse_numbers(arguments, 3, 3).map(|n| material.color = color(n[0], n[1], n[2])),
+        "Ka" => parse_numbers(arguments, 3, 3)
+            .map(|n| material.ambient = ambient_from(&color(n[0], n[1], n[2]))),
+        "Ks" => parse_numbers(arguments, 3, 3)
+            .map(|n| material.specular = average(&color(n[0], n[1], n[2]))),
+        "Ns" => parse_numbers(arguments, 1, 1).map(|n| material.shininess = n[0]),
+        "Ni" => parse_numbers(arguments, 1, 1).map(|n| material.refractive_index = n[0]),
+        //d is opacity, Tr is its complement
+        "d" => parse_numbers(arguments, 1, 1).map(|n| material.transparency = 1.0 - n[0]),
+        "Tr" => parse_numbers(arguments, 1, 1).map(|n| material.transparency = n[0]),
+        _ => return None,
+    };
+    Some(result)
+}
+
+//never panics, anything that can't be used is reported in warnings with its line number
+pub fn parse_mtl(source: &str) -> MtlLibrary {
+    let mut library = MtlLibrary::new();
+    let mut current: Option<String> = None;
+
+    for (index, raw_line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let line = match raw_line.find('#') {
+            Some(comment) => &raw_line[..comment],
+            None => raw_line,
+        };
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let (keyword, arguments) = match tokens.split_first() {
+            Some((keyword, arguments)) => (*keyword, arguments),
+            None => continue,
+        };
+        let mut warn = |kind: ObjWarningKind, message: String| {
+            library.warnings.push(ObjWarning {
+                line: line_number,
+                kind,
+                message,
+            })
+        };
+
+        if keyword == "newmtl" {
+            if arguments.is_empty() {
+                warn(
+                    ObjWarningKind::Malformed,
+                    "'newmtl' needs a name".to_string(),
+                );
+                continue;
+            }
+            let name = arguments.join(" ");
+            library
+                .materials
+                .insert(name.clone(), Material::default_material());
+            current = Some(name);
+            continue;
+        }
+
+        let name = match &current {
+            Some(name) => name.clone(),
+            None => {
+                let message = format!("'{}' comes before any newmtl", keyword);
+                warn(ObjWarningKind::Malformed, message);
+                continue;
+            }
+        };
+
+        if keyword == "map_Kd" {
+            match arguments.last() {
+                Some(path) => {
+                    library.diffuse_maps.insert(name, path.to_string());
+                }
+                None => warn(
+                    ObjWarningKind::Malformed,
+                    "'map_Kd' needs a path".to_string(),
+                ),
+            }
+            continue;
+        }
+
+        let material = library.materials.get_mut(&name).unwrap();
+        match apply_statement(material, keyword, arguments) {
+            Some(Ok(())) => {}
+            Some(Err(message)) => warn(ObjWarningKind::Malformed, message),
+            None => {
+                let message = format!("unsupported statement '{}'", keyword);
+                warn(ObjWarningKind::Ignored, message);
+            }
+        }
+    }
+    library
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_material() {
+        let source = "# a glassy red\n\
+                      newmtl red_glass\n\
+                      Ka 0.3 0.0 0.0\n\
+                      Kd 0.8 0.1 0.1\n\
+                      Ks 0.9 0.9 0.6\n\
+                      Ns 250\n\
+                      Ni 1.5\n\
+                      d 0.25\n";
+        let library = parse_mtl(source);
+        assert!(library.warnings.is_empty());
+        let mut expected = Material::default_material();
+        expected.ambient = 0.01;
+        expected.color = color(0.8, 0.1, 0.1);
+        expected.specular = 0.8;
+        expected.shininess = 250.0;
+        expected.refractive_index = 1.5;
+        expected.transparency = 0.75;
+        assert_eq!(library.material("red_glass").unwrap(), &expected);
+    }
+
+    #[test]
+    fn test_missing_statements_keep_defaults() {
+        let library = parse_mtl("newmtl plain\nKd 0 0 1\nnewmtl seethrough\nTr 0.4\n");
+        let plain = library.material("plain").unwrap();
+        assert_eq!(plain.color, color(0.0, 0.0, 1.0));
+        assert_eq!(plain.ambient, 0.1);
+        assert_eq!(plain.transparency, 0.0);
+        assert_eq!(library.material("seethrough").unwrap().transparency, 0.4);
+        assert!(library.material("missing").is_none());
+    }
+
+    #[test]
+    fn test_exporter_ambient_keeps_default() {
+        //blender writes white, plenty of others write black
+        let library = parse_mtl("newmtl white\nKa 1.0 1.0 1.0\nnewmtl black\nKa 0 0 0\n");
+        let default = Material::default_material().ambient;
+        assert_eq!(library.material("white").unwrap().ambient, default);
+        assert_eq!(library.material("black").unwrap().ambient, default);
+    }
+
+    #[test]
+    fn test_diffuse_map_is_recorded() {
+        let library = parse_mtl("newmtl brick\nmap_Kd -bm 1 textures/brick.png\n");
+        assert_eq!(library.diffuse_maps["brick"], "textures/brick.png");
+        assert_eq!(
+            library.material("brick").unwrap().color,
+            color(1.0, 1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_bad_lines_are_reported() {
+        let source = "Kd 1 0 0\nnewmtl a\nKd 1 0\nNs shiny\nillum 2\nnewmtl\n";
+        let library = parse_mtl(source);
+        let found: Vec<(usize, ObjWarningKind)> = library
+            .warnings
+            .iter()
+            .map(|warning| (warning.line, warning.kind))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, ObjWarningKind::Malformed),
+                (3, ObjWarningKind::Malformed),
+                (4, ObjWarningKind::Malformed),
+                (5, ObjWarningKind::Ignored),
+                (6, ObjWarningKind::Malformed),
+            ]
+        );
+        assert_eq!(library.materials.len(), 1);
+    }
+}
@@ -0,0 +1,301 @@
+#![allow(dead_code)]
+use crate::tuple::Tuple;
+
+//3d gradient noise. the permutation table is shuffled from the seed, so the same seed
+//always gives the same values on every machine
+#[derive(Debug, Clone, PartialEq)]
+pub struct Noise {
+    seed: u64,
+    //0..256 shuffled, then repeated so lookups can overflow past 255 without wrapping
+    permutation: Vec<usize>,
+}
+
+//the twelve edge midpoints of a cube, used as simplex gradients
+const GRADIENTS: [[f64; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
+
+impl Noise {
+    pub fn new(seed: u64) -> Noise {
+        let mut table: Vec<usize> = (0..256).collect();
+        let mut state = seed;
+        //fisher-yates with splitmix64 as the random source
+        for index in (1..table.len()).rev() {
+            let other = (split_mix(&mut state) % (index as u64 + 1)) as usize;
+            table.swap(index, other);
+        }
+        let mut permutation = table.clone();
+        permutation.extend(table);
+        Noise { seed, permutation }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn hash(&self, x: i64, y: i64, z: i64) -> usize {
+        let p = &self.permutation;
+        p[p[p[(x & 255) as usize] + (y & 255) as usize] + (z & 255) as usize]
+    }
+
+    //improved perlin noise, roughly -1 to 1 and zero on every lattice point
+    pub fn perlin(&self, point: &Tuple) -> f64 {
+        let (x, y, z) = (point.x(), point.y(), point.z());
+        let (xi, yi, zi) = (x.floor() as i64, y.floor() as i64, z.floor() as i64);
+        let (xf, yf, zf) = (x - x.floor(), y - y.floor(), z - z.floor());
+        let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+
+        let corner = |dx: i64, dy: i64, dz: i64| {
+            let hash = self.hash(xi + dx, yi + dy, zi + dz);
+            perlin_gradient(hash, xf - dx as f64, yf - dy as f64, zf - dz as f64)
+        };
+        let x00 = lerp(corner(0, 0, 0), corner(1, 0, 0), u);
+        let x10 = lerp(corner(0, 1, 0), corner(1, 1, 0), u);
+        let x01 = lerp(corner(0, 0, 1), corner(1, 0, 1), u);
+        let x11 = lerp(corner(0, 1, 1), corner(1, 1, 1), u);
+        lerp(lerp(x00, x10, v), lerp(x01, x11, v), w)
+    }
+
+    //simplex noise, roughly -1 to 1 with fewer axis aligned artifacts than perlin
+    pub fn simplex(&self, point: &Tuple) -> f64 {
+        const SKEW: f64 = 1.0 / 3.0;
+        const UNSKEW: f64 = 1.0 / 6.0;
+        let (x, y, z) = (point.x(), point.y(), point.z());
+
+        //find the simplex cell the point is in
+        let s = (x + y + z) * SKEW;
+        let (i, j, k) = ((x + s).floor(), (y + s).floor(), (z + s).floor());
+        let t = (i + j + k) * UNSKEW;
+        let x0 = x - (i - t);
+        let y0 = y - (j - t);
+        let z0 = z - (k - t);
+
+        //which of the six tetrahedra, ordered by the largest offset
+        let (i1, j1, k1, i2, j2, k2) = if x0 >= y0 {
+            if y0 >= z0 {
+                (1, 0, 0, 1, 1, 0)
+            } else if x0 >= z0 {
+                (1, 0, 0, 1, 0, 1)
+            } else {
+                (0, 0, 1, 1, 0, 1)
+            }
+        } else if y0 < z0 {
+            (0, 0, 1, 0, 1, 1)
+        } else if x0 < z0 {
+            (0, 1, 0, 0, 1, 1)
+        } else {
+            (0, 1, 0, 1, 1, 0)
+        };
+
+        let offsets = [
+            (0, 0, 0, x0, y0, z0),
+            (
+                i1,
+                j1,
+                k1,
+                x0 - i1 as f64 + UNSKEW,
+                y0 - j1 as f64 + UNSKEW,
+                z0 - k1 as f64 + UNSKEW,
+            ),
+            (
+                i2,
+                j2,
+                k2,
+                x0 - i2 as f64 + 2.0 * UNSKEW,
+                y0 - j2 as f64 + 2.0 * UNSKEW,
+                z0 - k2 as f64 + 2.0 * UNSKEW,
+            ),
+            (
+                1,
+                1,
+                1,
+                x0 - 1.0 + 3.0 * UNSKEW,
+                y0 - 1.0 + 3.0 * UNSKEW,
+                z0 - 1.0 + 3.0 * UNSKEW,
+            ),
+        ];
+
+        let (i, j, k) = (i as i64, j as i64, k as i64);
+        let mut total = 0.0;
+        for (di, dj, dk, dx, dy, dz) in offsets {
+            let falloff = 0.6 - dx * dx - dy * dy - dz * dz;
+            if falloff > 0.0 {
+                let gradient = GRADIENTS[self.hash(i + di, j + dj, k + dk) % 12];
+                let dot = gradient[0] * dx + gradient[1] * dy + gradient[2] * dz;
+                total += falloff.powi(4) * dot;
+            }
+        }
+        //scales the sum to roughly -1 to 1
+        32.0 * total
+    }
+
+    //fractal brownian motion, octaves of perlin noise at doubling frequency and halving
+    //amplitude, normalised back to roughly -1 to 1
+    pub fn fbm(&self, point: &Tuple, octaves: usize) -> f64 {
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        let mut range = 0.0;
+        for _ in 0..octaves {
+            total += amplitude * self.perlin(&scale_point(point, frequency));
+            range += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        if range == 0.0 {
+            return 0.0;
+        }
+        total / range
+    }
+
+    //like fbm but summing absolute values, so roughly 0 to 1 with sharp creases
+    pub fn turbulence(&self, point: &Tuple, octaves: usize) -> f64 {
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        let mut range = 0.0;
+        for _ in 0..octaves {
+            total += amplitude * self.perlin(&scale_point(point, frequency)).abs();
+            range += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        if range == 0.0 {
+            return 0.0;
+        }
+        total / range
+    }
+}
+
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + t * (b - a)
+}
+
+//picks one of twelve edge directions from the low bits of the hash
+fn perlin_gradient(hash: usize, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { v } else { -v };
+    u + v
+}
+
+fn scale_point(point: &Tuple, factor: f64) -> Tuple {
+    Tuple::set_point(point.x() * factor, point.y() * factor, point.z() * factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_points() -> Vec<Tuple> {
+        let mut points = Vec::new();
+        for i in 0..400 {
+            let f = i as f64;
+            points.push(Tuple::set_point(
+                f * 0.137 - 20.0,
+                f * 0.291 - 50.0,
+                f * 0.053 + 3.0,
+            ));
+        }
+        points
+    }
+
+    #[test]
+    fn test_noise_is_reproducible() {
+        let a = Noise::new(42);
+        let b = Noise::new(42);
+        let point = Tuple::set_point(1.3, -4.7, 0.25);
+        assert_eq!(a, b);
+        assert_eq!(a.perlin(&point), b.perlin(&point));
+        assert_eq!(a.simplex(&point), b.simplex(&point));
+        assert_eq!(a.fbm(&point, 5), b.fbm(&point, 5));
+    }
+
+    #[test]
+    fn test_seeds_give_different_noise() {
+        let a = Noise::new(1);
+        let b = Noise::new(2);
+        let differs = sample_points()
+            .iter()
+            .any(|point| (a.perlin(point) - b.perlin(point)).abs() > 0.01);
+        assert!(differs);
+    }
+
+    #[test]
+    fn test_perlin_is_zero_on_lattice() {
+        let noise = Noise::new(7);
+        for x in -3..3 {
+            for y in -3..3 {
+                let point = Tuple::set_point(x as f64, y as f64, 2.0);
+                assert_eq!(noise.perlin(&point), 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_noise_ranges() {
+        let noise = Noise::new(99);
+        let mut spread = 0.0_f64;
+        for point in sample_points() {
+            let perlin = noise.perlin(&point);
+            let simplex = noise.simplex(&point);
+            let fbm = noise.fbm(&point, 6);
+            let turbulence = noise.turbulence(&point, 6);
+            assert!((-1.0..=1.0).contains(&perlin));
+            assert!((-1.0..=1.0).contains(&simplex));
+            assert!((-1.0..=1.0).contains(&fbm));
+            assert!((0.0..=1.0).contains(&turbulence));
+            spread = spread.max(simplex.abs());
+        }
+        //the noise actually varies
+        assert!(spread > 0.2);
+    }
+
+    #[test]
+    fn test_noise_is_continuous() {
+        let noise = Noise::new(5);
+        let a = Tuple::set_point(0.5, 0.5, 0.5);
+        let b = Tuple::set_point(0.5001, 0.5, 0.5);
+        assert!((noise.perlin(&a) - noise.perlin(&b)).abs() < 0.001);
+        assert!((noise.simplex(&a) - noise.simplex(&b)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_zero_octaves() {
+        let noise = Noise::new(0);
+        let point = Tuple::set_point(0.3, 0.2, 0.1);
+        assert_eq!(noise.fbm(&point, 0), 0.0);
+        assert_eq!(noise.turbulence(&point, 0), 0.0);
+    }
+}
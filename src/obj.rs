@@ -0,0 +1,585 @@
+#![allow(dead_code)]
+use crate::group::Group;
+use crate::material::Material;
+use crate::mtl::MtlLibrary;
+use crate::shape::Shape;
+use crate::triangle::SmoothTriangle;
+use crate::triangle::Triangle;
+use crate::tuple::Tuple;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ObjWarningKind {
+    //a statement we don't support, skipped on purpose
+    Ignored,
+    //a statement we support but couldn't make sense of
+    Malformed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjWarning {
+    pub line: usize,
+    pub kind: ObjWarningKind,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub struct ObjGroup {
+    pub name: String,
+    pub triangles: Vec<Box<dyn Shape>>,
+}
+
+//vertices, normals and texture coordinates are stored 0-based,
+//the first group is the unnamed default group faces land in before any g or o
+#[derive(Debug)]
+pub struct ObjFile {
+    pub vertices: Vec<Tuple>,
+    pub normals: Vec<Tuple>,
+    pub texture_coords: Vec<(f64, f64)>,
+    pub groups: Vec<ObjGroup>,
+    //names from mtllib statements, in file order
+    pub material_libraries: Vec<String>,
+    pub materials: MtlLibrary,
+    pub warnings: Vec<ObjWarning>,
+}
+
+//one corner of a face, the indices already resolved to 0-based
+struct FaceVertex {
+    vertex: usize,
+    normal: Option<usize>,
+}
+
+impl ObjFile {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> std::io::Result<ObjFile> {
+        let source = fs::read_to_string(&path)?;
+        //mtllib paths are relative to the obj file
+        let directory = path.as_ref().parent().unwrap_or(Path::new(""));
+        Ok(parse(&source, MtlLibrary::new(), Some(directory)))
+    }
+
+    pub fn default_group(&self) -> &ObjGroup {
+        &self.groups[0]
+    }
+
+    pub fn group(&self, name: &str) -> Option<&ObjGroup> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.groups.iter().map(|group| group.triangles.len()).sum()
+    }
+
+    //every triangle from every group, ready to hand to a world
+    pub fn into_shapes(self) -> Vec<Box<dyn Shape>> {
+        self.groups
+            .into_iter()
+            .flat_map(|group| group.triangles)
+            .collect()
+    }
+
+    //one group holding a child group per named obj group, default group faces sit directly in it
+    pub fn into_group(self) -> Group {
+        let mut root = Group::set_group();
+        for obj_group in self.groups {
+            if obj_group.name.is_empty() {
+                for triangle in obj_group.triangles {
+                    root.add_child(triangle);
+                }
+            } else if !obj_group.triangles.is_empty() {
+                let mut group = Group::set_group();
+                for triangle in obj_group.triangles {
+                    group.add_child(triangle);
+                }
+                root.add_child(Box::new(group));
+            }
+        }
+        root
+    }
+
+    fn warn(&mut self, line: usize, kind: ObjWarningKind, message: String) {
+        self.warnings.push(ObjWarning {
+            line,
+            kind,
+            message,
+        });
+    }
+
+    fn start_group(&mut self, name: &str) -> usize {
+        match self.groups.iter().position(|group| group.name == name) {
+            Some(index) => index,
+            None => {
+                self.groups.push(ObjGroup {
+                    name: name.to_string(),
+                    triangles: Vec::new(),
+                });
+                self.groups.len() - 1
+            }
+        }
+    }
+
+    //obj indices are 1-based, negative ones count back from the end
+    fn resolve_index(index: &str, count: usize) -> Result<usize, String> {
+        let value: i64 = index
+            .parse()
+            .map_err(|_| format!("'{}' is not an index", index))?;
+        let resolved = if value > 0 {
+            value - 1
+        } else if value < 0 {
+            count as i64 + value
+        } else {
+            return Err("indices start at 1".to_string());
+        };
+        if resolved < 0 || resolved as usize >= count {
+            return Err(format!("index {} is out of range", value));
+        }
+        Ok(resolved as usize)
+    }
+
+    //handles v, v/vt, v//vn and v/vt/vn
+    fn parse_face_vertex(&self, token: &str) -> Result<FaceVertex, String> {
+        let mut parts = token.split('/');
+        let vertex = Self::resolve_index(parts.next().unwrap_or(""), self.vertices.len())?;
+        if let Some(texture) = parts.next() {
+            if !texture.is_empty() {
+                Self::resolve_index(texture, self.texture_coords.len())?;
+            }
+        }
+        let normal = match parts.next() {
+            Some(normal) if !normal.is_empty() => {
+                Some(Self::resolve_index(normal, self.normals.len())?)
+            }
+            _ => None,
+        };
+        if parts.next().is_some() {
+            return Err(format!("'{}' has too many parts", token));
+        }
+        Ok(FaceVertex { vertex, normal })
+    }
+
+    //polygons are split into a fan of triangles around the first vertex
+    fn fan_triangulation(
+        &self,
+        corners: &[FaceVertex],
+        material: &Material,
+    ) -> Vec<Box<dyn Shape>> {
+        let mut triangles: Vec<Box<dyn Shape>> = Vec::new();
+        for index in 1..(corners.len() - 1) {
+            let (a, b, c) = (&corners[0], &corners[index], &corners[index + 1]);
+            let p1 = self.vertices[a.vertex].clone();
+            let p2 = self.vertices[b.vertex].clone();
+            let p3 = self.vertices[c.vertex].clone();
+            match (a.normal, b.normal, c.normal) {
+                (Some(n1), Some(n2), Some(n3)) => {
+                    let mut triangle = SmoothTriangle::set_smooth_triangle(
+                        p1,
+                        p2,
+                        p3,
+                        self.normals[n1].clone(),
+                        self.normals[n2].clone(),
+                        self.normals[n3].clone(),
+                    );
+                    triangle.material = material.clone();
+                    triangles.push(Box::new(triangle));
+                }
+                _ => {
+                    let mut triangle = Triangle::set_triangle(p1, p2, p3);
+                    triangle.material = material.clone();
+                    triangles.push(Box::new(triangle));
+                }
+            }
+        }
+        triangles
+    }
+}
+
+pub fn parse_numbers(
+    arguments: &[&str],
+    minimum: usize,
+    maximum: usize,
+) -> Result<Vec<f64>, String> {
+    if arguments.len() < minimum || arguments.len() > maximum {
+        return Err(format!(
+            "expected {} to {} numbers, found {}",
+            minimum,
+            maximum,
+            arguments.len()
+        ));
+    }
+    arguments
+        .iter()
+        .map(|argument| {
+            argument
+                .parse::<f64>()
+                .map_err(|_| format!("'{}' is not a number", argument))
+        })
+        .collect()
+}
+
+//never panics, anything that can't be used is reported in warnings with its line number
+pub fn parse_obj(source: &str) -> ObjFile {
+    parse(source, MtlLibrary::new(), None)
+}
+
+//for obj sources that don't live on disk, usemtl looks names up in materials
+pub fn parse_obj_with_materials(source: &str, materials: MtlLibrary) -> ObjFile {
+    parse(source, materials, None)
+}
+
+//mtllib statements are only loaded when there's a directory to load them from
+fn parse(source: &str, materials: MtlLibrary, directory: Option<&Path>) -> ObjFile {
+    let mut obj = ObjFile {
+        vertices: Vec::new(),
+        normals: Vec::new(),
+        texture_coords: Vec::new(),
+        groups: Vec::new(),
+        material_libraries: Vec::new(),
+        materials,
+        warnings: Vec::new(),
+    };
+    let mut current_group = obj.start_group("");
+    let mut current_material = Material::default_material();
+
+    for (index, raw_line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let line = match raw_line.find('#') {
+            Some(comment) => &raw_line[..comment],
+            None => raw_line,
+        };
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let (keyword, arguments) = match tokens.split_first() {
+            Some((keyword, arguments)) => (*keyword, arguments),
+            None => continue,
+        };
+
+        match keyword {
+            "v" => match parse_numbers(arguments, 3, 4) {
+                Ok(numbers) => obj
+                    .vertices
+                    .push(Tuple::set_point(numbers[0], numbers[1], numbers[2])),
+                Err(message) => obj.warn(line_number, ObjWarningKind::Malformed, message),
+            },
+            "vn" => match parse_numbers(arguments, 3, 3) {
+                Ok(numbers) => obj
+                    .normals
+                    .push(Tuple::set_vector(numbers[0], numbers[1], numbers[2])),
+                Err(message) => obj.warn(line_number, ObjWarningKind::Malformed, message),
+            },
+            "vt" => match parse_numbers(arguments, 1, 3) {
+                Ok(numbers) => {
+                    let v = numbers.get(1).copied().unwrap_or(0.0);
+                    obj.texture_coords.push((numbers[0], v));
+                }
+                Err(message) => obj.warn(line_number, ObjWarningKind::Malformed, message),
+            },
+            "f" => {
+                if arguments.len() < 3 {
+                    let message = format!("a face needs 3 vertices, found {}", arguments.len());
+                    obj.warn(line_number, ObjWarningKind::Malformed, message);
+                    continue;
+                }
+                let corners: Result<Vec<FaceVertex>, String> = arguments
+                    .iter()
+                    .map(|token| obj.parse_face_vertex(token))
+                    .collect();
+                match corners {
+                    Ok(corners) => {
+                        let triangles = obj.fan_triangulation(&corners, &current_material);
+                        obj.groups[current_group].triangles.extend(triangles);
+                    }
+                    Err(message) => obj.warn(line_number, ObjWarningKind::Malformed, message),
+                }
+            }
+            "g" | "o" => {
+                if arguments.is_empty() {
+                    let message = format!("'{}' needs a name", keyword);
+                    obj.warn(line_number, ObjWarningKind::Malformed, message);
+                    continue;
+                }
+                current_group = obj.start_group(&arguments.join(" "));
+            }
+            "mtllib" => {
+                if arguments.is_empty() {
+                    let message = "'mtllib' needs a file name".to_string();
+                    obj.warn(line_number, ObjWarningKind::Malformed, message);
+                    continue;
+                }
+                for name in arguments {
+                    obj.material_libraries.push(name.to_string());
+                    let directory = match directory {
+                        Some(directory) => directory,
+                        None => continue,
+                    };
+                    match MtlLibrary::from_file(directory.join(name)) {
+                        Ok(mut library) => {
+                            //report the mtl's problems against the line that loaded it
+                            for warning in library.warnings.drain(..) {
+                                let message =
+                                    format!("{} line {}: {}", name, warning.line, warning.message);
+                                obj.warn(line_number, warning.kind, message);
+                            }
+                            obj.materials.merge(library);
+                        }
+                        Err(error) => {
+                            let message = format!("could not read '{}': {}", name, error);
+                            obj.warn(line_number, ObjWarningKind::Malformed, message);
+                        }
+                    }
+                }
+            }
+            "usemtl" => {
+                let name = arguments.join(" ");
+                match obj.materials.material(&name) {
+                    Some(material) => current_material = material.clone(),
+                    None => {
+                        let message = format!("unknown material '{}'", name);
+                        obj.warn(line_number, ObjWarningKind::Malformed, message);
+                        current_material = Material::default_material();
+                    }
+                }
+            }
+            _ => {
+                let message = format!("unsupported statement '{}'", keyword);
+                obj.warn(line_number, ObjWarningKind::Ignored, message);
+            }
+        }
+    }
+    obj
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::color;
+    use crate::ray::Ray;
+
+    #[test]
+    fn test_ignores_unrecognized_lines() {
+        let source = "There was a young lady named Bright\n\
+                      who traveled much faster than light.\n\
+                      \n\
+                      # just a comment\n\
+                      She set out one day\n";
+        let obj = parse_obj(source);
+        let lines: Vec<usize> = obj.warnings.iter().map(|warning| warning.line).collect();
+        assert_eq!(lines, vec![1, 2, 5]);
+        assert!(obj
+            .warnings
+            .iter()
+            .all(|warning| warning.kind == ObjWarningKind::Ignored));
+    }
+
+    #[test]
+    fn test_vertex_records() {
+        let source = "v -1 1 0\nv -1.0000 0.5000 0.0000\nv 1 0 0\nv 1 1 0\n";
+        let obj = parse_obj(source);
+        assert_eq!(obj.vertices.len(), 4);
+        assert_eq!(obj.vertices[0], Tuple::set_point(-1.0, 1.0, 0.0));
+        assert_eq!(obj.vertices[1], Tuple::set_point(-1.0, 0.5, 0.0));
+        assert_eq!(obj.vertices[2], Tuple::set_point(1.0, 0.0, 0.0));
+        assert_eq!(obj.vertices[3], Tuple::set_point(1.0, 1.0, 0.0));
+        assert!(obj.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_triangle_faces() {
+        let source = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\nf 1 2 3\nf 1 3 4\n";
+        let obj = parse_obj(source);
+        assert_eq!(obj.default_group().triangles.len(), 2);
+        assert_eq!(obj.triangle_count(), 2);
+        assert!(obj.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_polygon_fan() {
+        let source = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\nv 0 2 0\n\nf 1 2 3 4 5\n";
+        let obj = parse_obj(source);
+        assert_eq!(obj.default_group().triangles.len(), 3);
+
+        //the last triangle of the fan covers vertices 1, 4 and 5
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 1.5, -1.0),
+        );
+        let hits: usize = obj
+            .default_group()
+            .triangles
+            .iter()
+            .map(|triangle| triangle.intersect(&ray).len())
+            .sum();
+        assert_eq!(hits, 1);
+    }
+
+    #[test]
+    fn test_named_groups() {
+        let source = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\
+                      g FirstGroup\nf 1 2 3\n\
+                      o SecondGroup\nf 1 3 4\n\
+                      g FirstGroup\nf 2 3 4\n";
+        let obj = parse_obj(source);
+        assert_eq!(obj.default_group().triangles.len(), 0);
+        assert_eq!(obj.group("FirstGroup").unwrap().triangles.len(), 2);
+        assert_eq!(obj.group("SecondGroup").unwrap().triangles.len(), 1);
+        assert!(obj.group("ThirdGroup").is_none());
+        assert_eq!(obj.into_shapes().len(), 3);
+    }
+
+    #[test]
+    fn test_obj_file_into_group() {
+        let source = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\
+                      f 1 2 4\n\
+                      g FirstGroup\nf 1 2 3\n\
+                      g SecondGroup\nf 1 3 4\n\
+                      g EmptyGroup\n";
+        let group = parse_obj(source).into_group();
+        //the default group triangle plus one child group per non-empty named group
+        assert_eq!(group.children().len(), 3);
+
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.5, 0.5, -1.0),
+        );
+        assert_eq!(group.intersect(&ray).len(), 1);
+    }
+
+    #[test]
+    fn test_vertex_normal_records() {
+        let source = "vn 0 0 1\nvn 0.707 0 -0.707\nvn 1 2 3\n";
+        let obj = parse_obj(source);
+        assert_eq!(obj.normals.len(), 3);
+        assert_eq!(obj.normals[0], Tuple::set_vector(0.0, 0.0, 1.0));
+        assert_eq!(obj.normals[1], Tuple::set_vector(0.707, 0.0, -0.707));
+        assert_eq!(obj.normals[2], Tuple::set_vector(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_texture_coordinate_records() {
+        let source = "vt 0.5 0.25\nvt 0.75\n";
+        let obj = parse_obj(source);
+        assert_eq!(obj.texture_coords, vec![(0.5, 0.25), (0.75, 0.0)]);
+    }
+
+    #[test]
+    fn test_faces_with_normals_and_textures() {
+        let source = "v 0 1 0\nv -1 0 0\nv 1 0 0\n\
+                      vn -1 0 0\nvn 1 0 0\nvn 0 1 0\n\
+                      vt 0 0\n\
+                      f 1//3 2//1 3//2\n\
+                      f 1/1/3 2/1/1 3/1/2\n\
+                      f 1/1 2/1 3/1\n\
+                      f -3 -2 -1\n";
+        let obj = parse_obj(source);
+        assert!(obj.warnings.is_empty());
+        assert_eq!(obj.default_group().triangles.len(), 4);
+
+        //the first two faces have normals so they shade smoothly
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.5, -1.0),
+        );
+        let triangles = &obj.default_group().triangles;
+        let xs = triangles[0].intersect(&ray);
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        assert_eq!(comps.normal_vector, Tuple::set_vector(0.0, 1.0, 0.0));
+        let xs = triangles[3].intersect(&ray);
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        assert_eq!(comps.normal_vector, Tuple::set_vector(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_malformed_lines_are_reported() {
+        let source = "v 1 2\nv 0 0 0\nv 1 0 0\nv one 1 0\nv 0 1 0\n\
+                      f 1 2\nf 1 2 9\nf 1 x 3\nf 0 1 2\nvn 1 0\ng\nf 1 2 3\n";
+        let obj = parse_obj(source);
+        let lines: Vec<usize> = obj.warnings.iter().map(|warning| warning.line).collect();
+        assert_eq!(lines, vec![1, 4, 6, 7, 8, 9, 10, 11]);
+        assert!(obj
+            .warnings
+            .iter()
+            .all(|warning| warning.kind == ObjWarningKind::Malformed));
+        assert_eq!(obj.vertices.len(), 3);
+        assert_eq!(obj.triangle_count(), 1);
+    }
+
+    #[test]
+    fn test_usemtl_applies_materials() {
+        let materials = crate::mtl::parse_mtl(
+            "newmtl red
+Kd 1 0 0
+newmtl blue
+Kd 0 0 1
+",
+        );
+        let source = "mtllib scene.mtl
+v 0 1 0
+v -1 0 0
+v 1 0 0
+f 1 2 3
+usemtl red
+f 1 2 3
+usemtl blue
+f 1 2 3
+usemtl green
+f 1 2 3
+";
+        let obj = parse_obj_with_materials(source, materials);
+        assert_eq!(obj.material_libraries, vec!["scene.mtl".to_string()]);
+        //the face before any usemtl and the one after the unknown green keep the default
+        let colors: Vec<_> = obj
+            .default_group()
+            .triangles
+            .iter()
+            .map(|triangle| triangle.material().color.clone())
+            .collect();
+        assert_eq!(
+            colors,
+            vec![
+                color(1.0, 1.0, 1.0),
+                color(1.0, 0.0, 0.0),
+                color(0.0, 0.0, 1.0),
+                color(1.0, 1.0, 1.0),
+            ]
+        );
+        assert_eq!(obj.warnings.len(), 1);
+        assert_eq!(obj.warnings[0].line, 10);
+    }
+
+    #[test]
+    fn test_from_file_loads_mtllib() {
+        let directory = std::env::temp_dir().join(format!("obj_mtllib_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(
+            directory.join("shiny.mtl"),
+            "newmtl shiny
+Ns 300
+illum 2
+",
+        )
+        .unwrap();
+        fs::write(
+            directory.join("mesh.obj"),
+            "mtllib shiny.mtl missing.mtl
+usemtl shiny
+v 0 1 0
+v -1 0 0
+v 1 0 0
+f 1 2 3
+",
+        )
+        .unwrap();
+        let obj = ObjFile::from_file(directory.join("mesh.obj")).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(obj.default_group().triangles[0].material().shininess, 300.0);
+        //the unsupported illum and the missing library are both reported on line 1
+        let kinds: Vec<(usize, ObjWarningKind)> = obj
+            .warnings
+            .iter()
+            .map(|warning| (warning.line, warning.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![(1, ObjWarningKind::Ignored), (1, ObjWarningKind::Malformed)]
+        );
+    }
+}
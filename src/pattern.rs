@@ -0,0 +1,495 @@
+#![allow(dead_code)]
+use crate::color::Color;
+use crate::matrix::Matrix;
+use crate::noise::Noise;
+use crate::shape::Shape;
+use crate::texture::ImageTexture;
+use crate::tuple::Tuple;
+use std::f64::consts::PI;
+
+//how many octaves of noise the marble, wood and cloud patterns sum
+pub const TEXTURE_OCTAVES: usize = 5;
+
+//the slots of each kind are patterns themselves, so looks can be nested as deep as needed.
+//a plain color is a Solid pattern, a is used at the pattern origin
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternKind {
+    Solid(Color),
+    //alternates on x
+    Stripe(Box<Pattern>, Box<Pattern>),
+    //blends from a to b between x = 0 and x = 1, then repeats
+    Gradient(Box<Pattern>, Box<Pattern>),
+    //blends from a to b outward from the y axis, repeating every unit
+    RadialGradient(Box<Pattern>, Box<Pattern>),
+    //concentric rings around the y axis
+    Ring(Box<Pattern>, Box<Pattern>),
+    //alternating unit cubes
+    Checker(Box<Pattern>, Box<Pattern>),
+    //the average of both
+    Blend(Box<Pattern>, Box<Pattern>),
+    //jitters the point by up to scale before handing it to the pattern
+    Perturbed(Box<Pattern>, Noise, f64),
+    //veins along x, a sine wave pushed around by turbulence
+    Marble(Box<Pattern>, Box<Pattern>, Noise),
+    //rings around the y axis with noisy spacing
+    Wood(Box<Pattern>, Box<Pattern>, Noise),
+    //soft billows, b where the noise is highest
+    Clouds(Box<Pattern>, Box<Pattern>, Noise),
+    //an image wrapped on with a uv mapping
+    Texture(ImageTexture),
+}
+
+//a pattern has its own transform, applied on top of the object's or its parent pattern's
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pub kind: PatternKind,
+    transform: Matrix,
+    inverse: Matrix,
+}
+
+impl From<Color> for Pattern {
+    fn from(color: Color) -> Pattern {
+        Pattern::solid(color)
+    }
+}
+
+impl Pattern {
+    pub fn new(kind: PatternKind) -> Pattern {
+        Pattern {
+            kind,
+            transform: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+        }
+    }
+
+    pub fn solid(color: Color) -> Pattern {
+        Pattern::new(PatternKind::Solid(color))
+    }
+
+    pub fn stripe(a: impl Into<Pattern>, b: impl Into<Pattern>) -> Pattern {
+        Pattern::new(PatternKind::Stripe(Box::new(a.into()), Box::new(b.into())))
+    }
+
+    pub fn gradient(a: impl Into<Pattern>, b: impl Into<Pattern>) -> Pattern {
+        Pattern::new(PatternKind::Gradient(
+            Box::new(a.into()),
+            Box::new(b.into()),
+        ))
+    }
+
+    pub fn radial_gradient(a: impl Into<Pattern>, b: impl Into<Pattern>) -> Pattern {
+        Pattern::new(PatternKind::RadialGradient(
+            Box::new(a.into()),
+            Box::new(b.into()),
+        ))
+    }
+
+    pub fn ring(a: impl Into<Pattern>, b: impl Into<Pattern>) -> Pattern {
+        Pattern::new(PatternKind::Ring(Box::new(a.into()), Box::new(b.into())))
+    }
+
+    pub fn checker(a: impl Into<Pattern>, b: impl Into<Pattern>) -> Pattern {
+        Pattern::new(PatternKind::Checker(Box::new(a.into()), Box::new(b.into())))
+    }
+
+    pub fn blend(a: impl Into<Pattern>, b: impl Into<Pattern>) -> Pattern {
+        Pattern::new(PatternKind::Blend(Box::new(a.into()), Box::new(b.into())))
+    }
+
+    pub fn perturbed(pattern: impl Into<Pattern>, scale: f64, seed: u64) -> Pattern {
+        Pattern::new(PatternKind::Perturbed(
+            Box::new(pattern.into()),
+            Noise::new(seed),
+            scale,
+        ))
+    }
+
+    pub fn marble(a: impl Into<Pattern>, b: impl Into<Pattern>, seed: u64) -> Pattern {
+        Pattern::new(PatternKind::Marble(
+            Box::new(a.into()),
+            Box::new(b.into()),
+            Noise::new(seed),
+        ))
+    }
+
+    pub fn wood(a: impl Into<Pattern>, b: impl Into<Pattern>, seed: u64) -> Pattern {
+        Pattern::new(PatternKind::Wood(
+            Box::new(a.into()),
+            Box::new(b.into()),
+            Noise::new(seed),
+        ))
+    }
+
+    pub fn clouds(a: impl Into<Pattern>, b: impl Into<Pattern>, seed: u64) -> Pattern {
+        Pattern::new(PatternKind::Clouds(
+            Box::new(a.into()),
+            Box::new(b.into()),
+            Noise::new(seed),
+        ))
+    }
+
+    pub fn texture(texture: ImageTexture) -> Pattern {
+        Pattern::new(PatternKind::Texture(texture))
+    }
+
+    pub fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    pub fn set_transform(&mut self, transform: Matrix) {
+        self.inverse = transform.invert();
+        self.transform = transform;
+    }
+
+    //point is in this pattern's space
+    pub fn pattern_at(&self, point: &Tuple) -> Color {
+        match &self.kind {
+            PatternKind::Solid(color) => color.clone(),
+            PatternKind::Stripe(a, b) => pick(a, b, point.x().floor(), point),
+            PatternKind::Gradient(a, b) => mix(a, b, point.x() - point.x().floor(), point),
+            PatternKind::RadialGradient(a, b) => {
+                let distance = (point.x() * point.x() + point.z() * point.z()).sqrt();
+                mix(a, b, distance - distance.floor(), point)
+            }
+            PatternKind::Ring(a, b) => {
+                let distance = (point.x() * point.x() + point.z() * point.z()).sqrt();
+                pick(a, b, distance.floor(), point)
+            }
+            PatternKind::Checker(a, b) => {
+                let sum = point.x().floor() + point.y().floor() + point.z().floor();
+                pick(a, b, sum, point)
+            }
+            PatternKind::Blend(a, b) => mix(a, b, 0.5, point),
+            PatternKind::Perturbed(pattern, noise, scale) => {
+                //offset samples so each axis gets its own noise
+                let jitter_x = noise.perlin(point);
+                let jitter_y = noise.perlin(&offset(point, 5.2, 1.3, 7.1));
+                let jitter_z = noise.perlin(&offset(point, 1.7, 9.2, 3.4));
+                let jittered = offset(point, jitter_x * scale, jitter_y * scale, jitter_z * scale);
+                pattern.sub_pattern_at(&jittered)
+            }
+            PatternKind::Marble(a, b, noise) => {
+                let turbulence = noise.turbulence(point, TEXTURE_OCTAVES);
+                let vein = ((point.x() + 5.0 * turbulence) * PI).sin();
+                mix(a, b, 0.5 + 0.5 * vein, point)
+            }
+            PatternKind::Wood(a, b, noise) => {
+                let distance = (point.x() * point.x() + point.z() * point.z()).sqrt();
+                let grain = distance + 0.5 * noise.fbm(point, TEXTURE_OCTAVES);
+                mix(a, b, grain - grain.floor(), point)
+            }
+            PatternKind::Clouds(a, b, noise) => {
+                let density = 0.5 + 0.5 * noise.fbm(point, TEXTURE_OCTAVES);
+                mix(a, b, density.clamp(0.0, 1.0), point)
+            }
+            PatternKind::Texture(texture) => texture.color_at(point),
+        }
+    }
+
+    //point is in the parent pattern's space
+    fn sub_pattern_at(&self, point: &Tuple) -> Color {
+        self.pattern_at(&(&self.inverse * point))
+    }
+
+    //point is in world space, it goes through the object's transform then the pattern's
+    pub fn pattern_at_shape(&self, object: &dyn Shape, point: &Tuple) -> Color {
+        let object_point = object.inverse() * point;
+        self.sub_pattern_at(&object_point)
+    }
+}
+
+//a on even whole numbers, b on odd ones
+fn pick(a: &Pattern, b: &Pattern, whole: f64, point: &Tuple) -> Color {
+    if (whole as i64) % 2 == 0 {
+        a.sub_pattern_at(point)
+    } else {
+        b.sub_pattern_at(point)
+    }
+}
+
+fn offset(point: &Tuple, x: f64, y: f64, z: f64) -> Tuple {
+    Tuple::set_point(point.x() + x, point.y() + y, point.z() + z)
+}
+
+fn mix(a: &Pattern, b: &Pattern, fraction: f64, point: &Tuple) -> Color {
+    let from = a.sub_pattern_at(point);
+    let to = b.sub_pattern_at(point);
+    from.clone() + (to - from) * fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::color;
+    use crate::sphere::Sphere;
+    use crate::transform::scaling;
+    use crate::transform::translation;
+
+    fn white() -> Color {
+        color(1.0, 1.0, 1.0)
+    }
+
+    fn black() -> Color {
+        color(0.0, 0.0, 0.0)
+    }
+
+    fn point(x: f64, y: f64, z: f64) -> Tuple {
+        Tuple::set_point(x, y, z)
+    }
+
+    #[test]
+    fn test_stripe_pattern() {
+        let pattern = Pattern::stripe(white(), black());
+        //constant in y and z
+        assert_eq!(pattern.pattern_at(&point(0.0, 1.0, 0.0)), white());
+        assert_eq!(pattern.pattern_at(&point(0.0, 2.0, 0.0)), white());
+        assert_eq!(pattern.pattern_at(&point(0.0, 0.0, 1.0)), white());
+        assert_eq!(pattern.pattern_at(&point(0.0, 0.0, 2.0)), white());
+        //alternates in x
+        assert_eq!(pattern.pattern_at(&point(0.9, 0.0, 0.0)), white());
+        assert_eq!(pattern.pattern_at(&point(1.0, 0.0, 0.0)), black());
+        assert_eq!(pattern.pattern_at(&point(-0.1, 0.0, 0.0)), black());
+        assert_eq!(pattern.pattern_at(&point(-1.0, 0.0, 0.0)), black());
+        assert_eq!(pattern.pattern_at(&point(-1.1, 0.0, 0.0)), white());
+    }
+
+    #[test]
+    fn test_gradient_pattern() {
+        let pattern = Pattern::gradient(white(), black());
+        assert_eq!(pattern.pattern_at(&point(0.0, 0.0, 0.0)), white());
+        assert_eq!(
+            pattern.pattern_at(&point(0.25, 0.0, 0.0)),
+            color(0.75, 0.75, 0.75)
+        );
+        assert_eq!(
+            pattern.pattern_at(&point(0.5, 0.0, 0.0)),
+            color(0.5, 0.5, 0.5)
+        );
+        assert_eq!(
+            pattern.pattern_at(&point(0.75, 0.0, 0.0)),
+            color(0.25, 0.25, 0.25)
+        );
+    }
+
+    #[test]
+    fn test_ring_pattern() {
+        let pattern = Pattern::ring(white(), black());
+        assert_eq!(pattern.pattern_at(&point(0.0, 0.0, 0.0)), white());
+        assert_eq!(pattern.pattern_at(&point(1.0, 0.0, 0.0)), black());
+        assert_eq!(pattern.pattern_at(&point(0.0, 0.0, 1.0)), black());
+        assert_eq!(pattern.pattern_at(&point(0.708, 0.0, 0.708)), black());
+    }
+
+    #[test]
+    fn test_checker_pattern() {
+        let pattern = Pattern::checker(white(), black());
+        assert_eq!(pattern.pattern_at(&point(0.0, 0.0, 0.0)), white());
+        assert_eq!(pattern.pattern_at(&point(0.99, 0.0, 0.0)), white());
+        assert_eq!(pattern.pattern_at(&point(1.01, 0.0, 0.0)), black());
+        assert_eq!(pattern.pattern_at(&point(0.0, 0.99, 0.0)), white());
+        assert_eq!(pattern.pattern_at(&point(0.0, 1.01, 0.0)), black());
+        assert_eq!(pattern.pattern_at(&point(0.0, 0.0, 0.99)), white());
+        assert_eq!(pattern.pattern_at(&point(0.0, 0.0, 1.01)), black());
+    }
+
+    #[test]
+    fn test_pattern_with_object_transform() {
+        let mut object = Sphere::set_sphere(point(0.0, 0.0, 0.0), 1.0);
+        object.set_transform(scaling(2.0, 2.0, 2.0));
+        let pattern = Pattern::stripe(white(), black());
+        assert_eq!(
+            pattern.pattern_at_shape(&object, &point(1.5, 0.0, 0.0)),
+            white()
+        );
+    }
+
+    #[test]
+    fn test_pattern_with_pattern_transform() {
+        let object = Sphere::set_sphere(point(0.0, 0.0, 0.0), 1.0);
+        let mut pattern = Pattern::stripe(white(), black());
+        pattern.set_transform(scaling(2.0, 2.0, 2.0));
+        assert_eq!(
+            pattern.pattern_at_shape(&object, &point(1.5, 0.0, 0.0)),
+            white()
+        );
+    }
+
+    #[test]
+    fn test_pattern_with_both_transforms() {
+        let mut object = Sphere::set_sphere(point(0.0, 0.0, 0.0), 1.0);
+        object.set_transform(scaling(2.0, 2.0, 2.0));
+        let mut pattern = Pattern::stripe(white(), black());
+        pattern.set_transform(translation(0.5, 0.0, 0.0));
+        assert_eq!(
+            pattern.pattern_at_shape(&object, &point(2.5, 0.0, 0.0)),
+            white()
+        );
+    }
+
+    #[test]
+    fn test_solid_pattern_from_color() {
+        let pattern: Pattern = color(0.2, 0.4, 0.6).into();
+        assert_eq!(pattern, Pattern::solid(color(0.2, 0.4, 0.6)));
+        assert_eq!(
+            pattern.pattern_at(&point(7.0, -3.0, 2.5)),
+            color(0.2, 0.4, 0.6)
+        );
+    }
+
+    #[test]
+    fn test_radial_gradient_pattern() {
+        let pattern = Pattern::radial_gradient(white(), black());
+        assert_eq!(pattern.pattern_at(&point(0.0, 5.0, 0.0)), white());
+        assert_eq!(
+            pattern.pattern_at(&point(0.0, 0.0, 0.25)),
+            color(0.75, 0.75, 0.75)
+        );
+        assert_eq!(
+            pattern.pattern_at(&point(0.3, 0.0, 0.4)),
+            color(0.5, 0.5, 0.5)
+        );
+        assert_eq!(pattern.pattern_at(&point(1.0, 0.0, 0.0)), white());
+    }
+
+    #[test]
+    fn test_blend_pattern() {
+        let pattern = Pattern::blend(
+            Pattern::stripe(white(), black()),
+            Pattern::solid(color(0.0, 0.0, 1.0)),
+        );
+        assert_eq!(
+            pattern.pattern_at(&point(0.5, 0.0, 0.0)),
+            color(0.5, 0.5, 1.0)
+        );
+        assert_eq!(
+            pattern.pattern_at(&point(1.5, 0.0, 0.0)),
+            color(0.0, 0.0, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_checker_of_stripes() {
+        //each sub-pattern has its own transform relative to the checker
+        let red = color(1.0, 0.0, 0.0);
+        let mut stripes = Pattern::stripe(white(), black());
+        stripes.set_transform(scaling(0.25, 1.0, 1.0));
+        let pattern = Pattern::checker(stripes, red.clone());
+        assert_eq!(pattern.pattern_at(&point(0.1, 0.0, 0.0)), white());
+        assert_eq!(pattern.pattern_at(&point(0.3, 0.0, 0.0)), black());
+        assert_eq!(pattern.pattern_at(&point(1.1, 0.0, 0.0)), red);
+        assert_eq!(pattern.pattern_at(&point(2.3, 0.0, 0.0)), black());
+    }
+
+    #[test]
+    fn test_nested_transforms_stack() {
+        let mut object = Sphere::set_sphere(point(0.0, 0.0, 0.0), 1.0);
+        object.set_transform(scaling(2.0, 2.0, 2.0));
+        let mut inner = Pattern::stripe(white(), black());
+        inner.set_transform(translation(0.5, 0.0, 0.0));
+        let mut outer = Pattern::gradient(inner, black());
+        outer.set_transform(scaling(2.0, 2.0, 2.0));
+        //world 2.5 is object 1.25, outer 0.625 and inner 0.125
+        assert_eq!(
+            outer.pattern_at_shape(&object, &point(2.5, 0.0, 0.0)),
+            color(0.375, 0.375, 0.375)
+        );
+    }
+
+    #[test]
+    fn test_perturbed_pattern() {
+        let stripes = Pattern::stripe(white(), black());
+        //no jitter leaves the pattern as it was
+        let still = Pattern::perturbed(stripes.clone(), 0.0, 3);
+        for x in [-1.5, -0.2, 0.3, 0.9, 1.4, 2.7] {
+            let sample = point(x, 0.3, 0.6);
+            assert_eq!(still.pattern_at(&sample), stripes.pattern_at(&sample));
+        }
+        //with jitter some samples near stripe edges change colour
+        let jittered = Pattern::perturbed(stripes.clone(), 0.5, 3);
+        let changed = (0..100)
+            .map(|i| point(i as f64 * 0.173, i as f64 * 0.071, 0.4))
+            .any(|sample| jittered.pattern_at(&sample) != stripes.pattern_at(&sample));
+        assert!(changed);
+    }
+
+    #[test]
+    fn test_noise_patterns_are_reproducible() {
+        let looks = [
+            (
+                Pattern::marble(white(), black(), 11),
+                Pattern::marble(white(), black(), 11),
+            ),
+            (
+                Pattern::wood(white(), black(), 11),
+                Pattern::wood(white(), black(), 11),
+            ),
+            (
+                Pattern::clouds(white(), black(), 11),
+                Pattern::clouds(white(), black(), 11),
+            ),
+        ];
+        for (a, b) in looks {
+            assert_eq!(a, b);
+            for i in 0..50 {
+                let sample = point(i as f64 * 0.37, i as f64 * -0.21, i as f64 * 0.13);
+                let (ca, cb) = (a.pattern_at(&sample), b.pattern_at(&sample));
+                assert_eq!(ca.red.to_bits(), cb.red.to_bits());
+                //white to black keeps every channel equal and within range
+                assert!((0.0..=1.0).contains(&ca.red));
+                assert_eq!(ca.red, ca.green);
+            }
+        }
+    }
+
+    #[test]
+    fn test_noise_patterns_vary() {
+        let marble = Pattern::marble(white(), black(), 1);
+        let wood = Pattern::wood(white(), black(), 1);
+        let clouds = Pattern::clouds(white(), black(), 1);
+        for pattern in [marble, wood, clouds] {
+            let shades: Vec<f64> = (0..50)
+                .map(|i| {
+                    pattern
+                        .pattern_at(&point(i as f64 * 0.31, 0.2, i as f64 * 0.17))
+                        .red
+                })
+                .collect();
+            let lightest = shades.iter().cloned().fold(f64::MIN, f64::max);
+            let darkest = shades.iter().cloned().fold(f64::MAX, f64::min);
+            assert!(lightest - darkest > 0.3);
+        }
+    }
+
+    #[test]
+    fn test_texture_pattern_on_a_sphere() {
+        use crate::canvas::Canvas;
+        use crate::texture::TextureFilter;
+        use crate::uv::UvMapping;
+
+        //left half of the image red, right half blue
+        let mut image = Canvas::zero(4, 2);
+        for row in 0..2 {
+            for col in 0..4 {
+                let shade = if col < 2 {
+                    color(1.0, 0.0, 0.0)
+                } else {
+                    color(0.0, 0.0, 1.0)
+                };
+                image.write_pixel(row, col, shade);
+            }
+        }
+        let texture = ImageTexture::new(image, UvMapping::Spherical, TextureFilter::Nearest);
+        let pattern = Pattern::texture(texture);
+        let mut object = Sphere::set_sphere(point(0.0, 0.0, 0.0), 1.0);
+        object.set_transform(translation(0.0, 0.0, 10.0));
+        //u is 0.25 at +x and 0.75 at -x
+        assert_eq!(
+            pattern.pattern_at_shape(&object, &point(1.0, 0.0, 10.0)),
+            color(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            pattern.pattern_at_shape(&object, &point(-1.0, 0.0, 10.0)),
+            color(0.0, 0.0, 1.0)
+        );
+    }
+}
@@ -0,0 +1,151 @@
+#![allow(dead_code)]
+use crate::bounds::BoundingBox;
+use crate::intersection::Intersection;
+use crate::intersection::EPSILON;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+//the xz plane in object space, infinite in both directions
+#[derive(Debug, Clone)]
+pub struct Plane {
+    transform: Matrix,
+    inverse: Matrix,
+    pub material: Material,
+}
+
+impl Plane {
+    pub fn set_plane() -> Plane {
+        Plane {
+            transform: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+            material: Material::default_material(),
+        }
+    }
+}
+
+impl Shape for Plane {
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        //a ray parallel to the plane never hits it, a coplanar one is treated as a miss
+        if ray.direction.y().abs() < EPSILON {
+            return Vec::new();
+        }
+        let t = -ray.origin.y() / ray.direction.y();
+        vec![Intersection::new(t, self)]
+    }
+
+    fn local_normal_at(&self, _point: Tuple) -> Tuple {
+        Tuple::set_vector(0.0, 1.0, 0.0)
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Tuple::set_point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Tuple::set_point(f64::INFINITY, 0.0, f64::INFINITY),
+        )
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn inverse(&self) -> &Matrix {
+        &self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.inverse = transform.invert();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::rotate_z;
+    use crate::transform::translation;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_plane_normal_is_constant() {
+        let plane = Plane::set_plane();
+        let expected = Tuple::set_vector(0.0, 1.0, 0.0);
+        assert_eq!(
+            plane.local_normal_at(Tuple::set_point(0.0, 0.0, 0.0)),
+            expected
+        );
+        assert_eq!(
+            plane.local_normal_at(Tuple::set_point(10.0, 0.0, -10.0)),
+            expected
+        );
+        assert_eq!(
+            plane.local_normal_at(Tuple::set_point(-5.0, 0.0, 150.0)),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_plane_intersect_parallel() {
+        let plane = Plane::set_plane();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 10.0, 0.0),
+        );
+        assert_eq!(plane.local_intersect(&ray).len(), 0);
+    }
+
+    #[test]
+    fn test_plane_intersect_coplanar() {
+        let plane = Plane::set_plane();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, 0.0),
+        );
+        assert_eq!(plane.local_intersect(&ray).len(), 0);
+    }
+
+    #[test]
+    fn test_plane_intersect_from_above() {
+        let plane = Plane::set_plane();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, -1.0, 0.0),
+            Tuple::set_point(0.0, 1.0, 0.0),
+        );
+        let xs = plane.local_intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 1.0);
+    }
+
+    #[test]
+    fn test_plane_intersect_from_below() {
+        let plane = Plane::set_plane();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_point(0.0, -1.0, 0.0),
+        );
+        let xs = plane.local_intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 1.0);
+    }
+
+    #[test]
+    fn test_transformed_plane() {
+        let mut plane = Plane::set_plane();
+        plane.set_transform(translation(0.0, 0.0, 5.0) * rotate_z(PI / 2.0));
+        let ray = Ray::set_ray(
+            Tuple::set_vector(1.0, 0.0, 0.0),
+            Tuple::set_point(-3.0, 0.0, 5.0),
+        );
+        let xs = plane.intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 3.0);
+        let normal = plane.normal_at(Tuple::set_point(0.0, 2.0, 5.0));
+        assert_eq!(normal, Tuple::set_vector(-1.0, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,84 @@
+#![allow(dead_code)]
+use crate::bounds::BoundingBox;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::tuple::Tuple;
+use std::fmt::Debug;
+
+//send and sync so a world can be shared between render threads
+pub trait Shape: Debug + Send + Sync {
+    //return intersections with this shape, ray is in object space
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>>;
+
+    //point is in object space, the normal returned is too
+    fn local_normal_at(&self, point: Tuple) -> Tuple;
+
+    //shapes that interpolate their normals across the surface override this
+    fn local_normal_at_hit(&self, point: Tuple, _hit: &Intersection) -> Tuple {
+        self.local_normal_at(point)
+    }
+
+    fn transform(&self) -> &Matrix;
+
+    //implementors keep the inverse around so it isn't recomputed per ray
+    fn inverse(&self) -> &Matrix;
+
+    fn set_transform(&mut self, transform: Matrix);
+
+    fn material(&self) -> &Material;
+
+    //bounds in object space, before the transform
+    fn local_bounds(&self) -> BoundingBox;
+
+    //bounds in world space, used to cull shapes a ray can't reach
+    fn bounds(&self) -> BoundingBox {
+        self.local_bounds().transform(self.transform())
+    }
+
+    //whether other is this shape or somewhere inside it, groups and csg look through their children
+    fn includes(&self, other: &dyn Shape) -> bool {
+        std::ptr::addr_eq(self as *const Self, other as *const dyn Shape)
+    }
+
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let local_ray = ray.transform(self.inverse());
+        self.local_intersect(&local_ray)
+    }
+
+    fn normal_at(&self, point: Tuple) -> Tuple {
+        let local_point = self.inverse() * &point;
+        let local_normal = self.local_normal_at(local_point);
+        normal_to_world(self.inverse(), local_normal)
+    }
+
+    fn normal_at_hit(&self, point: Tuple, hit: &Intersection) -> Tuple {
+        let local_point = self.inverse() * &point;
+        let local_normal = self.local_normal_at_hit(local_point, hit);
+        normal_to_world(self.inverse(), local_normal)
+    }
+}
+
+//groups and csg have no surface of their own, so a normal asked of them comes from the child
+//that most likely holds the point. that's the one whose bounds are closest, then whose centre is
+pub fn nearest_child<'a>(
+    children: impl IntoIterator<Item = &'a dyn Shape>,
+    point: &Tuple,
+) -> Option<&'a dyn Shape> {
+    let key = |child: &dyn Shape| {
+        let bounds = child.bounds();
+        let centre = (bounds.centroid() - point.clone()).magnitude();
+        (bounds.distance_to(point), centre)
+    };
+    children.into_iter().min_by(|a, b| {
+        let (a, b) = (key(*a), key(*b));
+        a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1))
+    })
+}
+
+fn normal_to_world(inverse: &Matrix, local_normal: Tuple) -> Tuple {
+    //the inverse-transpose keeps normals perpendicular under non-uniform scaling
+    let world_normal = &inverse.transpose() * &local_normal;
+    Tuple::set_vector(world_normal.x(), world_normal.y(), world_normal.z()).normalize()
+}
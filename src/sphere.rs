@@ -0,0 +1,324 @@
+#![allow(dead_code)]
+use crate::bounds::BoundingBox;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+#[derive(Debug, Clone)]
+pub struct Sphere {
+    origin: Tuple,
+    radius: f64,
+    transform: Matrix,
+    inverse: Matrix,
+    pub material: Material,
+}
+
+impl Sphere {
+    pub fn set_sphere(origin: Tuple, radius: f64) -> Sphere {
+        Sphere {
+            origin,
+            radius,
+            transform: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+            material: Material::default_material(),
+        }
+    }
+
+    //radius 1 around the origin, the shape most scenes start from
+    pub fn unit() -> Sphere {
+        Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0)
+    }
+}
+
+//a unit sphere made of glass, for refraction scenes
+pub fn glass_sphere() -> Sphere {
+    let mut sphere = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+    sphere.material.transparency = 1.0;
+    sphere.material.refractive_index = 1.5;
+    sphere
+}
+
+impl Shape for Sphere {
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let sphere_to_ray = ray.origin.clone() - self.origin.clone();
+        let a = Tuple::dot(ray.direction.clone(), ray.direction.clone());
+        let b = 2.0 * Tuple::dot(sphere_to_ray.clone(), ray.direction.clone());
+        let c =
+            Tuple::dot(sphere_to_ray.clone(), sphere_to_ray.clone()) - self.radius * self.radius;
+
+        let discriminant: f64 = (b * b) - (4.0 * a * c);
+        if discriminant < 0.0 {
+            return Vec::new();
+        }
+        let t1 = (-b - f64::sqrt(discriminant)) / (2.0 * a);
+        let t2 = (-b + f64::sqrt(discriminant)) / (2.0 * a);
+        vec![Intersection::new(t1, self), Intersection::new(t2, self)]
+    }
+
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let vector_to_point = point - self.origin.clone();
+        vector_to_point.normalize()
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        let r = self.radius;
+        let (x, y, z) = (self.origin.x(), self.origin.y(), self.origin.z());
+        BoundingBox::new(
+            Tuple::set_point(x - r, y - r, z - r),
+            Tuple::set_point(x + r, y + r, z + r),
+        )
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn inverse(&self) -> &Matrix {
+        &self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.inverse = transform.invert();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::scaling;
+    use crate::transform::translation;
+    use std::f64::consts::FRAC_1_SQRT_2;
+    #[test]
+    fn test_intersection_2_points() {
+        let sphere_origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let test_sphere = Sphere::set_sphere(sphere_origin, 1.0);
+
+        let ray_origin = Tuple::set_point(0.0, 0.0, -5.0);
+        let ray_direction = Tuple::set_vector(0.0, 0.0, 1.0);
+        let test_ray = Ray::set_ray(ray_direction, ray_origin);
+        let return_value = test_sphere.intersect(&test_ray);
+        assert_eq!(return_value.len(), 2);
+        assert_eq!(return_value[0].t, 4.0);
+        assert_eq!(return_value[1].t, 6.0);
+    }
+
+    #[test]
+    fn test_intersection_tangent() {
+        // returns {4.0, 6.0 basically}
+        let sphere_origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let test_sphere = Sphere::set_sphere(sphere_origin, 1.0);
+
+        let ray_origin = Tuple::set_point(0.0, 1.0, -5.0);
+        let ray_direction = Tuple::set_vector(0.0, 0.0, 1.0);
+        let test_ray = Ray::set_ray(ray_direction, ray_origin);
+        let return_value = test_sphere.intersect(&test_ray);
+        assert_eq!(return_value.len(), 2);
+        assert_eq!(return_value[0].t, 5.0);
+        assert_eq!(return_value[1].t, 5.0);
+    }
+
+    #[test]
+    fn test_intersection_miss() {
+        let sphere_origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let test_sphere = Sphere::set_sphere(sphere_origin, 1.0);
+
+        let ray_origin = Tuple::set_point(0.0, 2.0, -5.0);
+        let ray_direction = Tuple::set_vector(0.0, 0.0, 1.0);
+        let test_ray = Ray::set_ray(ray_direction, ray_origin);
+        let return_value = test_sphere.intersect(&test_ray);
+        assert_eq!(return_value.len(), 0);
+    }
+
+    #[test]
+    fn test_intersection_inside_sphere() {
+        let sphere_origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let test_sphere = Sphere::set_sphere(sphere_origin, 1.0);
+
+        let ray_origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let ray_direction = Tuple::set_vector(0.0, 0.0, 1.0);
+        let test_ray = Ray::set_ray(ray_direction, ray_origin);
+        let return_value = test_sphere.intersect(&test_ray);
+        assert_eq!(return_value.len(), 2);
+        assert_eq!(return_value[0].t, -1.0);
+        assert_eq!(return_value[1].t, 1.0);
+    }
+
+    #[test]
+    fn test_intersection_behind_ray() {
+        let sphere_origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let test_sphere = Sphere::set_sphere(sphere_origin, 1.0);
+
+        let ray_origin = Tuple::set_point(0.0, 0.0, 5.0);
+        let ray_direction = Tuple::set_vector(0.0, 0.0, 1.0);
+        let test_ray = Ray::set_ray(ray_direction, ray_origin);
+        let return_value = test_sphere.intersect(&test_ray);
+        assert_eq!(return_value.len(), 2);
+        assert_eq!(return_value[0].t, -6.0);
+        assert_eq!(return_value[1].t, -4.0);
+    }
+
+    #[test]
+    fn test_sphere_normal_x_axis() {
+        let sphere_origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let test_sphere = Sphere::set_sphere(sphere_origin, 1.0);
+
+        let test_point = Tuple::set_point(1.0, 0.0, 0.0);
+        let actual_value = test_sphere.normal_at(test_point);
+        let expected_value = Tuple::set_vector(1.0, 0.0, 0.0);
+        assert_eq!(actual_value, expected_value);
+    }
+
+    #[test]
+    fn test_sphere_normal_y_axis() {
+        let sphere_origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let test_sphere = Sphere::set_sphere(sphere_origin, 1.0);
+
+        let test_point = Tuple::set_point(0.0, 1.0, 0.0);
+        let actual_value = test_sphere.normal_at(test_point);
+        let expected_value = Tuple::set_vector(0.0, 1.0, 0.0);
+        assert_eq!(actual_value, expected_value);
+    }
+
+    #[test]
+    fn test_sphere_normal_z_axis() {
+        let sphere_origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let test_sphere = Sphere::set_sphere(sphere_origin, 1.0);
+
+        let test_point = Tuple::set_point(0.0, 0.0, 1.0);
+        let actual_value = test_sphere.normal_at(test_point);
+        let expected_value = Tuple::set_vector(0.0, 0.0, 1.0);
+        assert_eq!(actual_value, expected_value);
+    }
+
+    #[test]
+    fn test_sphere_normal_nonaxial() {
+        let sphere_origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let test_sphere = Sphere::set_sphere(sphere_origin, 1.0);
+
+        let test_point = Tuple::set_point(
+            3.0_f64.sqrt() / 3.0,
+            3.0_f64.sqrt() / 3.0,
+            3.0_f64.sqrt() / 3.0,
+        );
+        let actual_value = test_sphere.normal_at(test_point);
+        let expected_value = Tuple::set_vector(
+            3.0_f64.sqrt() / 3.0,
+            3.0_f64.sqrt() / 3.0,
+            3.0_f64.sqrt() / 3.0,
+        );
+        assert_eq!(actual_value, expected_value);
+    }
+
+    #[test]
+    fn test_sphere_normal_is_normalized() {
+        let sphere_origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let test_sphere = Sphere::set_sphere(sphere_origin, 1.0);
+
+        let test_point = Tuple::set_point(
+            3.0_f64.sqrt() / 3.0,
+            3.0_f64.sqrt() / 3.0,
+            3.0_f64.sqrt() / 3.0,
+        );
+        let actual_value = test_sphere.normal_at(test_point);
+        let expected_value = actual_value.normalize();
+        assert_eq!(actual_value, expected_value);
+    }
+
+    #[test]
+    fn test_sphere_normal_not_origin() {
+        let sphere_origin = Tuple::set_point(0.0, 1.0, 0.0);
+        let test_sphere = Sphere::set_sphere(sphere_origin, 1.0);
+
+        let test_point = Tuple::set_point(0.0, 1.70711, -0.70711);
+        let actual_value = test_sphere.normal_at(test_point);
+        let expected_value = Tuple::set_vector(0.0, 0.70711, -0.70711);
+        assert_eq!(actual_value, expected_value);
+    }
+
+    #[test]
+    fn test_change_sphere_material() {
+        let mut test_sphere = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        let mut new_material = Material::default_material();
+        new_material.ambient = 1.0;
+        test_sphere.material = new_material.clone();
+        assert_eq!(test_sphere.material, new_material);
+    }
+
+    #[test]
+    fn test_sphere_default_transform() {
+        let test_sphere = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        assert_eq!(*test_sphere.transform(), Matrix::identity(4, 4));
+    }
+
+    #[test]
+    fn test_sphere_set_transform() {
+        let mut test_sphere = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        test_sphere.set_transform(translation(2.0, 3.0, 4.0));
+        assert_eq!(*test_sphere.transform(), translation(2.0, 3.0, 4.0));
+        assert_eq!(*test_sphere.inverse(), translation(-2.0, -3.0, -4.0));
+    }
+
+    #[test]
+    fn test_intersect_scaled_sphere() {
+        let mut test_sphere = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        test_sphere.set_transform(scaling(2.0, 2.0, 2.0));
+
+        let ray_origin = Tuple::set_point(0.0, 0.0, -5.0);
+        let ray_direction = Tuple::set_vector(0.0, 0.0, 1.0);
+        let test_ray = Ray::set_ray(ray_direction, ray_origin);
+        let return_value = test_sphere.intersect(&test_ray);
+        assert_eq!(return_value.len(), 2);
+        assert_eq!(return_value[0].t, 3.0);
+        assert_eq!(return_value[1].t, 7.0);
+    }
+
+    #[test]
+    fn test_intersect_translated_sphere() {
+        let mut test_sphere = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        test_sphere.set_transform(translation(5.0, 0.0, 0.0));
+
+        let ray_origin = Tuple::set_point(0.0, 0.0, -5.0);
+        let ray_direction = Tuple::set_vector(0.0, 0.0, 1.0);
+        let test_ray = Ray::set_ray(ray_direction, ray_origin);
+        let return_value = test_sphere.intersect(&test_ray);
+        assert_eq!(return_value.len(), 0);
+    }
+
+    #[test]
+    fn test_normal_translated_sphere() {
+        let mut test_sphere = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        test_sphere.set_transform(translation(0.0, 1.0, 0.0));
+
+        let test_point = Tuple::set_point(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+        let actual_value = test_sphere.normal_at(test_point);
+        let expected_value = Tuple::set_vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+        assert_eq!(actual_value, expected_value);
+    }
+
+    #[test]
+    fn test_normal_squashed_sphere() {
+        let mut test_sphere = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        test_sphere.set_transform(scaling(1.0, 0.5, 1.0));
+
+        let test_point = Tuple::set_point(0.0, 2.0_f64.sqrt() / 2.0, -(2.0_f64.sqrt()) / 2.0);
+        let actual_value = test_sphere.normal_at(test_point);
+        let expected_value = Tuple::set_vector(0.0, 0.97014, -0.24254);
+        assert_eq!(actual_value, expected_value);
+    }
+
+    #[test]
+    fn test_glass_sphere() {
+        let test_sphere = glass_sphere();
+        assert_eq!(*test_sphere.transform(), Matrix::identity(4, 4));
+        assert_eq!(test_sphere.material.transparency, 1.0);
+        assert_eq!(test_sphere.material.refractive_index, 1.5);
+    }
+}
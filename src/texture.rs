@@ -0,0 +1,124 @@
+#![allow(dead_code)]
+use crate::canvas::Canvas;
+use crate::color::Color;
+use crate::tuple::Tuple;
+use crate::uv::UvMapping;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextureFilter {
+    //the closest pixel, blocky up close
+    Nearest,
+    //weighted average of the four closest pixels
+    Bilinear,
+}
+
+//an image wrapped onto a surface. the canvas is shared so cloning a material stays cheap
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageTexture {
+    pub image: Arc<Canvas>,
+    pub mapping: UvMapping,
+    pub filter: TextureFilter,
+}
+
+impl ImageTexture {
+    pub fn new(image: Canvas, mapping: UvMapping, filter: TextureFilter) -> ImageTexture {
+        ImageTexture {
+            image: Arc::new(image),
+            mapping,
+            filter,
+        }
+    }
+
+    //point is in pattern space
+    pub fn color_at(&self, point: &Tuple) -> Color {
+        let (u, v) = self.mapping.map(point);
+        self.sample(u, v)
+    }
+
+    //u runs left to right and v bottom to top, both are clamped to 0..1
+    pub fn sample(&self, u: f64, v: f64) -> Color {
+        let (width, height) = (self.image.width(), self.image.height());
+        if width == 0 || height == 0 {
+            return Color::create_black();
+        }
+        let x = u.clamp(0.0, 1.0) * (width - 1) as f64;
+        let y = (1.0 - v.clamp(0.0, 1.0)) * (height - 1) as f64;
+        match self.filter {
+            TextureFilter::Nearest => self
+                .image
+                .read_pixel(y.round() as usize, x.round() as usize),
+            TextureFilter::Bilinear => {
+                let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+                let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+                let (fx, fy) = (x - x0 as f64, y - y0 as f64);
+                let top =
+                    self.image.read_pixel(y0, x0) * (1.0 - fx) + self.image.read_pixel(y0, x1) * fx;
+                let bottom =
+                    self.image.read_pixel(y1, x0) * (1.0 - fx) + self.image.read_pixel(y1, x1) * fx;
+                top * (1.0 - fy) + bottom * fy
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::color;
+
+    //black and white along the top row, red and green along the bottom
+    fn test_image() -> Canvas {
+        let mut image = Canvas::zero(2, 2);
+        image.write_pixel(0, 0, color(0.0, 0.0, 0.0));
+        image.write_pixel(0, 1, color(1.0, 1.0, 1.0));
+        image.write_pixel(1, 0, color(1.0, 0.0, 0.0));
+        image.write_pixel(1, 1, color(0.0, 1.0, 0.0));
+        image
+    }
+
+    #[test]
+    fn test_nearest_sampling() {
+        let texture = ImageTexture::new(test_image(), UvMapping::Planar, TextureFilter::Nearest);
+        //v = 1 is the top row of the image
+        assert_eq!(texture.sample(0.0, 1.0), color(0.0, 0.0, 0.0));
+        assert_eq!(texture.sample(1.0, 1.0), color(1.0, 1.0, 1.0));
+        assert_eq!(texture.sample(0.0, 0.0), color(1.0, 0.0, 0.0));
+        assert_eq!(texture.sample(0.8, 0.2), color(0.0, 1.0, 0.0));
+        assert_eq!(texture.sample(2.0, -1.0), color(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_bilinear_sampling() {
+        let texture = ImageTexture::new(test_image(), UvMapping::Planar, TextureFilter::Bilinear);
+        assert_eq!(texture.sample(0.0, 1.0), color(0.0, 0.0, 0.0));
+        assert_eq!(texture.sample(0.5, 1.0), color(0.5, 0.5, 0.5));
+        assert_eq!(texture.sample(0.0, 0.5), color(0.5, 0.0, 0.0));
+        assert_eq!(texture.sample(0.5, 0.5), color(0.5, 0.5, 0.25));
+        assert_eq!(texture.sample(1.0, 0.0), color(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_texture_uses_mapping() {
+        let texture = ImageTexture::new(test_image(), UvMapping::Planar, TextureFilter::Nearest);
+        //planar maps x to u and z to v
+        assert_eq!(
+            texture.color_at(&Tuple::set_point(0.9, 5.0, 0.9)),
+            color(1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            texture.color_at(&Tuple::set_point(0.1, 5.0, 0.1)),
+            color(1.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_empty_image_is_black() {
+        let texture = ImageTexture::new(
+            Canvas::zero(0, 0),
+            UvMapping::Planar,
+            TextureFilter::Nearest,
+        );
+        assert_eq!(texture.sample(0.5, 0.5), Color::create_black());
+    }
+}
@@ -1,4 +1,6 @@
+#![allow(dead_code)]
 use crate::matrix::Matrix;
+use crate::ray::Ray;
 use crate::tuple::Tuple;
 
 pub fn translation(x: f64, y: f64, z: f64) -> Matrix {
@@ -18,19 +20,79 @@ pub fn scaling(x: f64, y: f64, z: f64) -> Matrix {
     return return_matrix;
 }
 
-// fn rotate_x(degrees:f64) -> Matrix{
+pub fn rotate_x(radians: f64) -> Matrix {
+    let mut return_matrix = Matrix::identity(4, 4);
+    return_matrix.set_element(1, 1, radians.cos());
+    return_matrix.set_element(1, 2, -radians.sin());
+    return_matrix.set_element(2, 1, radians.sin());
+    return_matrix.set_element(2, 2, radians.cos());
+    return_matrix
+}
 
-// }
+pub fn rotate_y(radians: f64) -> Matrix {
+    let mut return_matrix = Matrix::identity(4, 4);
+    return_matrix.set_element(0, 0, radians.cos());
+    return_matrix.set_element(0, 2, radians.sin());
+    return_matrix.set_element(2, 0, -radians.sin());
+    return_matrix.set_element(2, 2, radians.cos());
+    return_matrix
+}
 
-// fn rotate_y(degrees:f64) -> Matrix{
+pub fn rotate_z(radians: f64) -> Matrix {
+    let mut return_matrix = Matrix::identity(4, 4);
+    return_matrix.set_element(0, 0, radians.cos());
+    return_matrix.set_element(0, 1, -radians.sin());
+    return_matrix.set_element(1, 0, radians.sin());
+    return_matrix.set_element(1, 1, radians.cos());
+    return_matrix
+}
 
-// }
+//rodrigues' rotation formula, the axis doesn't need to be normalized
+pub fn rotation(axis: Tuple, radians: f64) -> Matrix {
+    let axis = axis.normalize();
+    let (x, y, z) = (axis.x(), axis.y(), axis.z());
+    let cos = radians.cos();
+    let sin = radians.sin();
+    let t = 1.0 - cos;
 
-// fn rotate_z(degrees:f64) -> Matrix{
+    let mut return_matrix = Matrix::identity(4, 4);
+    return_matrix.set_element(0, 0, t * x * x + cos);
+    return_matrix.set_element(0, 1, t * x * y - sin * z);
+    return_matrix.set_element(0, 2, t * x * z + sin * y);
+    return_matrix.set_element(1, 0, t * x * y + sin * z);
+    return_matrix.set_element(1, 1, t * y * y + cos);
+    return_matrix.set_element(1, 2, t * y * z - sin * x);
+    return_matrix.set_element(2, 0, t * x * z - sin * y);
+    return_matrix.set_element(2, 1, t * y * z + sin * x);
+    return_matrix.set_element(2, 2, t * z * z + cos);
+    return_matrix
+}
 
-// }
+//the order the axis rotations are applied in, Xyz rotates about x first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EulerOrder {
+    Xyz,
+    Xzy,
+    Yxz,
+    Yzx,
+    Zxy,
+    Zyx,
+}
 
-fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix {
+pub fn euler_rotation(x: f64, y: f64, z: f64, order: EulerOrder) -> Matrix {
+    let (rx, ry, rz) = (rotate_x(x), rotate_y(y), rotate_z(z));
+    //matrices apply right to left, so the first rotation goes last
+    match order {
+        EulerOrder::Xyz => rz * ry * rx,
+        EulerOrder::Xzy => ry * rz * rx,
+        EulerOrder::Yxz => rz * rx * ry,
+        EulerOrder::Yzx => rx * rz * ry,
+        EulerOrder::Zxy => ry * rx * rz,
+        EulerOrder::Zyx => rx * ry * rz,
+    }
+}
+
+pub fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix {
     let mut return_matrix = Matrix::identity(4, 4);
     return_matrix.set_element(0, 1, xy);
     return_matrix.set_element(0, 2, xz);
@@ -42,9 +104,150 @@ fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix {
     return return_matrix;
 }
 
+//rotation taking +z to forward and +y as close to up as possible
+pub fn orientation(forward: Tuple, up: Tuple) -> Matrix {
+    let forward = forward.normalize();
+    let right = Tuple::cross(up.normalize(), forward.clone()).normalize();
+    let true_up = Tuple::cross(forward.clone(), right.clone());
+
+    let mut return_matrix = Matrix::identity(4, 4);
+    for (col, axis) in [right, true_up, forward].iter().enumerate() {
+        return_matrix.set_element(0, col, axis.x());
+        return_matrix.set_element(1, col, axis.y());
+        return_matrix.set_element(2, col, axis.z());
+    }
+    return_matrix
+}
+
+//places an object at from, with its +z axis pointing at to
+pub fn look_at(from: Tuple, to: Tuple, up: Tuple) -> Matrix {
+    let forward = to - from.clone();
+    translation(from.x(), from.y(), from.z()) * orientation(forward, up)
+}
+
+//moves the world so the eye sits at the origin looking down -z
+pub fn view_transform(from: Tuple, to: Tuple, up: Tuple) -> Matrix {
+    let forward = (to - from.clone()).normalize();
+    let left = Tuple::cross(forward.clone(), up.normalize());
+    let true_up = Tuple::cross(left.clone(), forward.clone());
+
+    let mut orientation = Matrix::identity(4, 4);
+    for (row, axis) in [left, true_up, -forward].iter().enumerate() {
+        orientation.set_element(row, 0, axis.x());
+        orientation.set_element(row, 1, axis.y());
+        orientation.set_element(row, 2, axis.z());
+    }
+    orientation * translation(-from.x(), -from.y(), -from.z())
+}
+
+//builds up a matrix in reading order, so
+//Transform::identity().rotate_x(a).scale(2.0, 2.0, 2.0) rotates first and then scales
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transform {
+    matrix: Matrix,
+    inverse: Matrix,
+    inverse_transpose: Matrix,
+}
+
+impl Transform {
+    pub fn identity() -> Transform {
+        Transform {
+            matrix: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+            inverse_transpose: Matrix::identity(4, 4),
+        }
+    }
+
+    pub fn from_matrix(matrix: Matrix) -> Transform {
+        let inverse = matrix.invert();
+        let inverse_transpose = inverse.transpose();
+        Transform {
+            matrix,
+            inverse,
+            inverse_transpose,
+        }
+    }
+
+    //applies operation after everything added so far
+    pub fn then(self, operation: Matrix) -> Transform {
+        let operation_inverse = operation.invert();
+        let inverse = &self.inverse * &operation_inverse;
+        let inverse_transpose = inverse.transpose();
+        Transform {
+            matrix: &operation * &self.matrix,
+            inverse,
+            inverse_transpose,
+        }
+    }
+
+    pub fn translate(self, x: f64, y: f64, z: f64) -> Transform {
+        self.then(translation(x, y, z))
+    }
+
+    pub fn scale(self, x: f64, y: f64, z: f64) -> Transform {
+        self.then(scaling(x, y, z))
+    }
+
+    pub fn rotate_x(self, radians: f64) -> Transform {
+        self.then(rotate_x(radians))
+    }
+
+    pub fn rotate_y(self, radians: f64) -> Transform {
+        self.then(rotate_y(radians))
+    }
+
+    pub fn rotate_z(self, radians: f64) -> Transform {
+        self.then(rotate_z(radians))
+    }
+
+    pub fn rotate(self, axis: Tuple, radians: f64) -> Transform {
+        self.then(rotation(axis, radians))
+    }
+
+    pub fn shear(self, xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Transform {
+        self.then(shearing(xy, xz, yx, yz, zx, zy))
+    }
+
+    pub fn matrix(&self) -> &Matrix {
+        &self.matrix
+    }
+
+    pub fn inverse(&self) -> &Matrix {
+        &self.inverse
+    }
+
+    pub fn inverse_transpose(&self) -> &Matrix {
+        &self.inverse_transpose
+    }
+
+    pub fn apply_point(&self, point: &Tuple) -> Tuple {
+        &self.matrix * point
+    }
+
+    pub fn apply_vector(&self, vector: &Tuple) -> Tuple {
+        &self.matrix * vector
+    }
+
+    pub fn apply_normal(&self, normal: &Tuple) -> Tuple {
+        let transformed = &self.inverse_transpose * normal;
+        Tuple::set_vector(transformed.x(), transformed.y(), transformed.z()).normalize()
+    }
+
+    pub fn apply_ray(&self, ray: &Ray) -> Ray {
+        ray.transform(&self.matrix)
+    }
+}
+
+impl From<Transform> for Matrix {
+    fn from(transform: Transform) -> Matrix {
+        transform.matrix
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::f64::consts::PI;
 
     #[test]
     fn test_translation() {
@@ -95,4 +298,274 @@ mod tests {
         let point_b = Tuple::set_vector(-2.0, 2.0, 2.0);
         assert_eq!(inv * point_a, point_b);
     }
+
+    #[test]
+    fn test_rotate_x() {
+        let point = Tuple::set_point(0.0, 1.0, 0.0);
+        let half_quarter = rotate_x(PI / 4.0);
+        let full_quarter = rotate_x(PI / 2.0);
+        let root = 2.0_f64.sqrt() / 2.0;
+        assert_eq!(
+            half_quarter * point.clone(),
+            Tuple::set_point(0.0, root, root)
+        );
+        assert_eq!(full_quarter * point, Tuple::set_point(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_rotate_x_inverse() {
+        let point = Tuple::set_point(0.0, 1.0, 0.0);
+        let inv = rotate_x(PI / 4.0).invert();
+        let root = 2.0_f64.sqrt() / 2.0;
+        assert_eq!(inv * point, Tuple::set_point(0.0, root, -root));
+    }
+
+    #[test]
+    fn test_rotate_y() {
+        let point = Tuple::set_point(0.0, 0.0, 1.0);
+        let half_quarter = rotate_y(PI / 4.0);
+        let full_quarter = rotate_y(PI / 2.0);
+        let root = 2.0_f64.sqrt() / 2.0;
+        assert_eq!(
+            half_quarter * point.clone(),
+            Tuple::set_point(root, 0.0, root)
+        );
+        assert_eq!(full_quarter * point, Tuple::set_point(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_rotate_z() {
+        let point = Tuple::set_point(0.0, 1.0, 0.0);
+        let half_quarter = rotate_z(PI / 4.0);
+        let full_quarter = rotate_z(PI / 2.0);
+        let root = 2.0_f64.sqrt() / 2.0;
+        assert_eq!(
+            half_quarter * point.clone(),
+            Tuple::set_point(-root, root, 0.0)
+        );
+        assert_eq!(full_quarter * point, Tuple::set_point(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_rotation_matches_axis_rotations() {
+        let x_axis = Tuple::set_vector(1.0, 0.0, 0.0);
+        let y_axis = Tuple::set_vector(0.0, 2.0, 0.0);
+        let z_axis = Tuple::set_vector(0.0, 0.0, -1.0);
+        assert_eq!(rotation(x_axis, 0.3), rotate_x(0.3));
+        assert_eq!(rotation(y_axis, 1.1), rotate_y(1.1));
+        assert_eq!(rotation(z_axis, 0.7), rotate_z(-0.7));
+    }
+
+    #[test]
+    fn test_rotation_arbitrary_axis() {
+        let axis = Tuple::set_vector(1.0, 1.0, 1.0);
+        let third_turn = rotation(axis, 2.0 * PI / 3.0);
+        let point = Tuple::set_point(1.0, 0.0, 0.0);
+        assert_eq!(third_turn * point, Tuple::set_point(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_euler_rotation_order() {
+        let xyz = euler_rotation(0.1, 0.2, 0.3, EulerOrder::Xyz);
+        let zyx = euler_rotation(0.1, 0.2, 0.3, EulerOrder::Zyx);
+        assert_eq!(xyz, rotate_z(0.3) * rotate_y(0.2) * rotate_x(0.1));
+        assert_eq!(zyx, rotate_x(0.1) * rotate_y(0.2) * rotate_z(0.3));
+        assert_ne!(xyz, zyx);
+    }
+
+    #[test]
+    fn test_euler_rotation_single_axis() {
+        let point = Tuple::set_point(0.0, 1.0, 0.0);
+        let only_z = euler_rotation(0.0, 0.0, PI / 2.0, EulerOrder::Yzx);
+        assert_eq!(only_z * point, Tuple::set_point(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_shearing() {
+        let point = Tuple::set_point(2.0, 3.0, 4.0);
+        let cases = [
+            (
+                shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+                Tuple::set_point(5.0, 3.0, 4.0),
+            ),
+            (
+                shearing(0.0, 1.0, 0.0, 0.0, 0.0, 0.0),
+                Tuple::set_point(6.0, 3.0, 4.0),
+            ),
+            (
+                shearing(0.0, 0.0, 1.0, 0.0, 0.0, 0.0),
+                Tuple::set_point(2.0, 5.0, 4.0),
+            ),
+            (
+                shearing(0.0, 0.0, 0.0, 1.0, 0.0, 0.0),
+                Tuple::set_point(2.0, 7.0, 4.0),
+            ),
+            (
+                shearing(0.0, 0.0, 0.0, 0.0, 1.0, 0.0),
+                Tuple::set_point(2.0, 3.0, 6.0),
+            ),
+            (
+                shearing(0.0, 0.0, 0.0, 0.0, 0.0, 1.0),
+                Tuple::set_point(2.0, 3.0, 7.0),
+            ),
+        ];
+        for (shear, expected) in cases {
+            assert_eq!(shear * point.clone(), expected);
+        }
+    }
+
+    #[test]
+    fn test_chained_transformations() {
+        let point = Tuple::set_point(1.0, 0.0, 1.0);
+        let chained = translation(10.0, 5.0, 7.0) * scaling(5.0, 5.0, 5.0) * rotate_x(PI / 2.0);
+        assert_eq!(chained * point, Tuple::set_point(15.0, 0.0, 7.0));
+    }
+
+    #[test]
+    fn test_orientation_default() {
+        let forward = Tuple::set_vector(0.0, 0.0, 1.0);
+        let up = Tuple::set_vector(0.0, 1.0, 0.0);
+        assert_eq!(orientation(forward, up), Matrix::identity(4, 4));
+    }
+
+    #[test]
+    fn test_orientation_points_z_forward() {
+        let forward = Tuple::set_vector(1.0, 0.0, 0.0);
+        let up = Tuple::set_vector(0.0, 1.0, 0.0);
+        let orient = orientation(forward, up);
+        let z_axis = Tuple::set_vector(0.0, 0.0, 1.0);
+        let y_axis = Tuple::set_vector(0.0, 1.0, 0.0);
+        assert_eq!(orient.clone() * z_axis, Tuple::set_vector(1.0, 0.0, 0.0));
+        assert_eq!(orient * y_axis, Tuple::set_vector(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_look_at() {
+        let from = Tuple::set_point(1.0, 2.0, 3.0);
+        let to = Tuple::set_point(1.0, 2.0, -7.0);
+        let up = Tuple::set_vector(0.0, 1.0, 0.0);
+        let placed = look_at(from, to, up);
+        let origin = Tuple::set_point(0.0, 0.0, 0.0);
+        let ahead = Tuple::set_point(0.0, 0.0, 2.0);
+        assert_eq!(placed.clone() * origin, Tuple::set_point(1.0, 2.0, 3.0));
+        assert_eq!(placed * ahead, Tuple::set_point(1.0, 2.0, 1.0));
+    }
+
+    #[test]
+    fn test_transform_identity() {
+        let identity = Transform::identity();
+        let point = Tuple::set_point(1.0, 2.0, 3.0);
+        assert_eq!(identity.apply_point(&point), point);
+        assert_eq!(*identity.inverse(), Matrix::identity(4, 4));
+    }
+
+    #[test]
+    fn test_transform_reading_order() {
+        let chained = Transform::identity()
+            .rotate_x(PI / 2.0)
+            .scale(5.0, 5.0, 5.0)
+            .translate(10.0, 5.0, 7.0);
+        let point = Tuple::set_point(1.0, 0.0, 1.0);
+        assert_eq!(
+            chained.apply_point(&point),
+            Tuple::set_point(15.0, 0.0, 7.0)
+        );
+        assert_eq!(
+            *chained.matrix(),
+            translation(10.0, 5.0, 7.0) * scaling(5.0, 5.0, 5.0) * rotate_x(PI / 2.0)
+        );
+    }
+
+    #[test]
+    fn test_transform_cached_inverse() {
+        let chained = Transform::identity()
+            .shear(1.0, 0.0, 0.0, 0.0, 0.0, 1.0)
+            .rotate(Tuple::set_vector(1.0, 1.0, 0.0), 0.5)
+            .scale(2.0, 3.0, 4.0)
+            .translate(1.0, -2.0, 3.0);
+        assert_eq!(*chained.inverse(), chained.matrix().invert());
+        assert_eq!(
+            *chained.inverse_transpose(),
+            chained.matrix().invert().transpose()
+        );
+        assert_eq!(chained.matrix() * chained.inverse(), Matrix::identity(4, 4));
+    }
+
+    #[test]
+    fn test_transform_from_matrix() {
+        let from_matrix = Transform::from_matrix(translation(1.0, 2.0, 3.0));
+        let built = Transform::identity().translate(1.0, 2.0, 3.0);
+        assert_eq!(from_matrix, built);
+        assert_eq!(Matrix::from(built), translation(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_transform_apply_vector() {
+        let moved = Transform::identity()
+            .translate(5.0, -3.0, 2.0)
+            .scale(2.0, 2.0, 2.0);
+        let vector = Tuple::set_vector(1.0, 2.0, 3.0);
+        assert_eq!(
+            moved.apply_vector(&vector),
+            Tuple::set_vector(2.0, 4.0, 6.0)
+        );
+    }
+
+    #[test]
+    fn test_transform_apply_normal() {
+        let squash = Transform::identity().scale(1.0, 0.5, 1.0);
+        let normal = Tuple::set_vector(0.0, 1.0, -1.0);
+        let expected = Tuple::set_vector(0.0, 2.0, -1.0).normalize();
+        assert_eq!(squash.apply_normal(&normal), expected);
+    }
+
+    #[test]
+    fn test_transform_apply_ray() {
+        let moved = Transform::identity().scale(2.0, 3.0, 4.0);
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_point(1.0, 2.0, 3.0),
+        );
+        let transformed = moved.apply_ray(&ray);
+        assert_eq!(transformed.origin, Tuple::set_point(2.0, 6.0, 12.0));
+        assert_eq!(transformed.direction, Tuple::set_vector(0.0, 3.0, 0.0));
+    }
+
+    #[test]
+    fn test_view_transform_default() {
+        let from = Tuple::set_point(0.0, 0.0, 0.0);
+        let to = Tuple::set_point(0.0, 0.0, -1.0);
+        let up = Tuple::set_vector(0.0, 1.0, 0.0);
+        assert_eq!(view_transform(from, to, up), Matrix::identity(4, 4));
+    }
+
+    #[test]
+    fn test_view_transform_positive_z() {
+        let from = Tuple::set_point(0.0, 0.0, 0.0);
+        let to = Tuple::set_point(0.0, 0.0, 1.0);
+        let up = Tuple::set_vector(0.0, 1.0, 0.0);
+        assert_eq!(view_transform(from, to, up), scaling(-1.0, 1.0, -1.0));
+    }
+
+    #[test]
+    fn test_view_transform_moves_world() {
+        let from = Tuple::set_point(0.0, 0.0, 8.0);
+        let to = Tuple::set_point(0.0, 0.0, 0.0);
+        let up = Tuple::set_vector(0.0, 1.0, 0.0);
+        assert_eq!(view_transform(from, to, up), translation(0.0, 0.0, -8.0));
+    }
+
+    #[test]
+    fn test_view_transform_arbitrary() {
+        let from = Tuple::set_point(1.0, 3.0, 2.0);
+        let to = Tuple::set_point(4.0, -2.0, 8.0);
+        let up = Tuple::set_vector(1.0, 1.0, 0.0);
+        let expected = Matrix::set(&vec![
+            vec![-0.50709, 0.50709, 0.67612, -2.36643],
+            vec![0.76772, 0.60609, 0.12122, -2.82843],
+            vec![-0.35857, 0.59761, -0.71714, 0.0],
+            vec![0.0, 0.0, 0.0, 1.0],
+        ]);
+        assert_eq!(view_transform(from, to, up), expected);
+    }
 }
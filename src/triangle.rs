@@ -0,0 +1,338 @@
+#![allow(dead_code)]
+use crate::bounds::BoundingBox;
+use crate::intersection::Intersection;
+use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+#[derive(Debug, Clone)]
+pub struct Triangle {
+    pub p1: Tuple,
+    pub p2: Tuple,
+    pub p3: Tuple,
+    pub e1: Tuple,
+    pub e2: Tuple,
+    pub normal: Tuple,
+    transform: Matrix,
+    inverse: Matrix,
+    pub material: Material,
+}
+
+impl Triangle {
+    pub fn set_triangle(p1: Tuple, p2: Tuple, p3: Tuple) -> Triangle {
+        let e1 = p2.clone() - p1.clone();
+        let e2 = p3.clone() - p1.clone();
+        let normal = Tuple::cross(e2.clone(), e1.clone()).normalize();
+        Triangle {
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal,
+            transform: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+            material: Material::default_material(),
+        }
+    }
+}
+
+//a triangle whose normal is blended from one normal per vertex
+#[derive(Debug, Clone)]
+pub struct SmoothTriangle {
+    pub p1: Tuple,
+    pub p2: Tuple,
+    pub p3: Tuple,
+    pub n1: Tuple,
+    pub n2: Tuple,
+    pub n3: Tuple,
+    pub e1: Tuple,
+    pub e2: Tuple,
+    transform: Matrix,
+    inverse: Matrix,
+    pub material: Material,
+}
+
+impl SmoothTriangle {
+    pub fn set_smooth_triangle(
+        p1: Tuple,
+        p2: Tuple,
+        p3: Tuple,
+        n1: Tuple,
+        n2: Tuple,
+        n3: Tuple,
+    ) -> SmoothTriangle {
+        let e1 = p2.clone() - p1.clone();
+        let e2 = p3.clone() - p1.clone();
+        SmoothTriangle {
+            p1,
+            p2,
+            p3,
+            n1,
+            n2,
+            n3,
+            e1,
+            e2,
+            transform: Matrix::identity(4, 4),
+            inverse: Matrix::identity(4, 4),
+            material: Material::default_material(),
+        }
+    }
+}
+
+//möller-trumbore, returns t along with the barycentric u and v of the hit
+fn triangle_intersect(ray: &Ray, p1: &Tuple, e1: &Tuple, e2: &Tuple) -> Option<(f64, f64, f64)> {
+    let dir_cross_e2 = Tuple::cross(ray.direction.clone(), e2.clone());
+    let determinant = Tuple::dot(e1.clone(), dir_cross_e2.clone());
+    //the determinant grows with the edge and direction lengths, so the parallel cutoff does too,
+    //otherwise small triangles would never be hit
+    let tolerance = f64::EPSILON * e1.magnitude() * e2.magnitude() * ray.direction.magnitude();
+    if determinant.abs() <= tolerance {
+        return None;
+    }
+
+    let f = 1.0 / determinant;
+    let p1_to_origin = ray.origin.clone() - p1.clone();
+    let u = f * Tuple::dot(p1_to_origin.clone(), dir_cross_e2);
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let origin_cross_e1 = Tuple::cross(p1_to_origin, e1.clone());
+    let v = f * Tuple::dot(ray.direction.clone(), origin_cross_e1.clone());
+    if v < 0.0 || (u + v) > 1.0 {
+        return None;
+    }
+
+    let t = f * Tuple::dot(e2.clone(), origin_cross_e1);
+    Some((t, u, v))
+}
+
+impl Shape for Triangle {
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        match triangle_intersect(ray, &self.p1, &self.e1, &self.e2) {
+            Some((t, u, v)) => vec![Intersection::with_uv(t, self, u, v)],
+            None => Vec::new(),
+        }
+    }
+
+    fn local_normal_at(&self, _point: Tuple) -> Tuple {
+        self.normal.clone()
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::empty();
+        bounds.add_point(&self.p1);
+        bounds.add_point(&self.p2);
+        bounds.add_point(&self.p3);
+        bounds
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn inverse(&self) -> &Matrix {
+        &self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.inverse = transform.invert();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+}
+
+impl Shape for SmoothTriangle {
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        match triangle_intersect(ray, &self.p1, &self.e1, &self.e2) {
+            Some((t, u, v)) => vec![Intersection::with_uv(t, self, u, v)],
+            None => Vec::new(),
+        }
+    }
+
+    //without a hit there is nothing to interpolate with, so fall back to the flat normal
+    fn local_normal_at(&self, _point: Tuple) -> Tuple {
+        Tuple::cross(self.e2.clone(), self.e1.clone()).normalize()
+    }
+
+    fn local_normal_at_hit(&self, _point: Tuple, hit: &Intersection) -> Tuple {
+        self.n2.clone() * hit.u + self.n3.clone() * hit.v + self.n1.clone() * (1.0 - hit.u - hit.v)
+    }
+
+    fn local_bounds(&self) -> BoundingBox {
+        let mut bounds = BoundingBox::empty();
+        bounds.add_point(&self.p1);
+        bounds.add_point(&self.p2);
+        bounds.add_point(&self.p3);
+        bounds
+    }
+
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn inverse(&self) -> &Matrix {
+        &self.inverse
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.inverse = transform.invert();
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_triangle() -> Triangle {
+        Triangle::set_triangle(
+            Tuple::set_point(0.0, 1.0, 0.0),
+            Tuple::set_point(-1.0, 0.0, 0.0),
+            Tuple::set_point(1.0, 0.0, 0.0),
+        )
+    }
+
+    fn test_smooth_triangle() -> SmoothTriangle {
+        SmoothTriangle::set_smooth_triangle(
+            Tuple::set_point(0.0, 1.0, 0.0),
+            Tuple::set_point(-1.0, 0.0, 0.0),
+            Tuple::set_point(1.0, 0.0, 0.0),
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_vector(-1.0, 0.0, 0.0),
+            Tuple::set_vector(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn test_triangle_struct() {
+        let triangle = test_triangle();
+        assert_eq!(triangle.e1, Tuple::set_vector(-1.0, -1.0, 0.0));
+        assert_eq!(triangle.e2, Tuple::set_vector(1.0, -1.0, 0.0));
+        assert_eq!(triangle.normal, Tuple::set_vector(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_triangle_normal_is_constant() {
+        let triangle = test_triangle();
+        let n1 = triangle.local_normal_at(Tuple::set_point(0.0, 0.5, 0.0));
+        let n2 = triangle.local_normal_at(Tuple::set_point(-0.5, 0.75, 0.0));
+        let n3 = triangle.local_normal_at(Tuple::set_point(0.5, 0.25, 0.0));
+        assert_eq!(n1, triangle.normal);
+        assert_eq!(n2, triangle.normal);
+        assert_eq!(n3, triangle.normal);
+    }
+
+    #[test]
+    fn test_triangle_parallel_ray() {
+        let triangle = test_triangle();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_point(0.0, -1.0, -2.0),
+        );
+        assert_eq!(triangle.local_intersect(&ray).len(), 0);
+    }
+
+    #[test]
+    fn test_triangle_misses_edges() {
+        let triangle = test_triangle();
+        let origins = [
+            Tuple::set_point(1.0, 1.0, -2.0),
+            Tuple::set_point(-1.0, 1.0, -2.0),
+            Tuple::set_point(0.0, -1.0, -2.0),
+        ];
+        for origin in origins {
+            let ray = Ray::set_ray(Tuple::set_vector(0.0, 0.0, 1.0), origin);
+            assert_eq!(triangle.local_intersect(&ray).len(), 0);
+        }
+    }
+
+    #[test]
+    fn test_triangle_hit() {
+        let triangle = test_triangle();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.5, -2.0),
+        );
+        let xs = triangle.local_intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 2.0);
+    }
+
+    #[test]
+    fn test_small_triangle_hit() {
+        //edges of a few millimetres, common in model files
+        let triangle = Triangle::set_triangle(
+            Tuple::set_point(0.0, 0.005, 0.0),
+            Tuple::set_point(-0.005, 0.0, 0.0),
+            Tuple::set_point(0.005, 0.0, 0.0),
+        );
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0025, -2.0),
+        );
+        let xs = triangle.local_intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 2.0);
+        //a ray along the surface still misses
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_point(0.0, -1.0, 0.0),
+        );
+        assert_eq!(triangle.local_intersect(&ray).len(), 0);
+    }
+
+    #[test]
+    fn test_smooth_triangle_struct() {
+        let triangle = test_smooth_triangle();
+        assert_eq!(triangle.p1, Tuple::set_point(0.0, 1.0, 0.0));
+        assert_eq!(triangle.n1, Tuple::set_vector(0.0, 1.0, 0.0));
+        assert_eq!(triangle.e1, Tuple::set_vector(-1.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn test_smooth_triangle_stores_uv() {
+        let triangle = test_smooth_triangle();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(-0.2, 0.3, -2.0),
+        );
+        let xs = triangle.local_intersect(&ray);
+        assert_eq!(xs.len(), 1);
+        assert!((xs[0].u - 0.45).abs() < 0.0001);
+        assert!((xs[0].v - 0.25).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_smooth_triangle_interpolates_normal() {
+        let triangle = test_smooth_triangle();
+        let hit = Intersection::with_uv(1.0, &triangle, 0.45, 0.25);
+        let normal = triangle.normal_at_hit(Tuple::set_point(0.0, 0.0, 0.0), &hit);
+        assert_eq!(normal, Tuple::set_vector(-0.5547, 0.83205, 0.0));
+    }
+
+    #[test]
+    fn test_prepare_computations_smooth_normal() {
+        let triangle = test_smooth_triangle();
+        let hit = Intersection::with_uv(1.0, &triangle, 0.45, 0.25);
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(-0.2, 0.3, -2.0),
+        );
+        let comps = hit.prepare_computations(&ray, &[hit]);
+        assert_eq!(
+            comps.normal_vector,
+            Tuple::set_vector(-0.5547, 0.83205, 0.0)
+        );
+    }
+}
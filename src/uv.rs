@@ -0,0 +1,207 @@
+#![allow(dead_code)]
+use crate::tuple::Tuple;
+use std::f64::consts::PI;
+
+//ways of flattening a 3d point onto a 0..1 u, v square. points are in pattern space,
+//spherical and cylindrical expect a unit shape centred on the origin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UvMapping {
+    Spherical,
+    //the xz plane, repeating every unit
+    Planar,
+    //around the y axis, v repeats every unit of height
+    Cylindrical,
+    //the unit cube, each face gets a sixth of a horizontal strip
+    Cube,
+}
+
+//in the order their images sit along a cube map strip
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CubeFace {
+    Left,
+    Front,
+    Right,
+    Back,
+    Up,
+    Down,
+}
+
+impl UvMapping {
+    pub fn map(&self, point: &Tuple) -> (f64, f64) {
+        match self {
+            UvMapping::Spherical => spherical_map(point),
+            UvMapping::Planar => planar_map(point),
+            UvMapping::Cylindrical => cylindrical_map(point),
+            UvMapping::Cube => {
+                let (face, u, v) = cube_map(point);
+                ((face as usize as f64 + u) / 6.0, v)
+            }
+        }
+    }
+}
+
+pub fn spherical_map(point: &Tuple) -> (f64, f64) {
+    let theta = point.x().atan2(point.z());
+    let radius = (point.x() * point.x() + point.y() * point.y() + point.z() * point.z()).sqrt();
+    let phi = (point.y() / radius).acos();
+    //theta runs -pi to pi, flipped so u grows counterclockwise seen from above
+    let raw_u = theta / (2.0 * PI);
+    (1.0 - (raw_u + 0.5), 1.0 - phi / PI)
+}
+
+pub fn planar_map(point: &Tuple) -> (f64, f64) {
+    (point.x().rem_euclid(1.0), point.z().rem_euclid(1.0))
+}
+
+pub fn cylindrical_map(point: &Tuple) -> (f64, f64) {
+    let theta = point.x().atan2(point.z());
+    let raw_u = theta / (2.0 * PI);
+    (1.0 - (raw_u + 0.5), point.y().rem_euclid(1.0))
+}
+
+//the face is picked by the largest coordinate
+pub fn face_from_point(point: &Tuple) -> CubeFace {
+    let (x, y, z) = (point.x(), point.y(), point.z());
+    let largest = x.abs().max(y.abs()).max(z.abs());
+    if largest == x {
+        CubeFace::Right
+    } else if largest == -x {
+        CubeFace::Left
+    } else if largest == y {
+        CubeFace::Up
+    } else if largest == -y {
+        CubeFace::Down
+    } else if largest == z {
+        CubeFace::Front
+    } else {
+        CubeFace::Back
+    }
+}
+
+//u and v within the face, each face seen from outside the cube
+pub fn cube_map(point: &Tuple) -> (CubeFace, f64, f64) {
+    let (x, y, z) = (point.x(), point.y(), point.z());
+    let face = face_from_point(point);
+    let (u, v) = match face {
+        CubeFace::Front => ((x + 1.0).rem_euclid(2.0), (y + 1.0).rem_euclid(2.0)),
+        CubeFace::Back => ((1.0 - x).rem_euclid(2.0), (y + 1.0).rem_euclid(2.0)),
+        CubeFace::Left => ((z + 1.0).rem_euclid(2.0), (y + 1.0).rem_euclid(2.0)),
+        CubeFace::Right => ((1.0 - z).rem_euclid(2.0), (y + 1.0).rem_euclid(2.0)),
+        CubeFace::Up => ((x + 1.0).rem_euclid(2.0), (1.0 - z).rem_euclid(2.0)),
+        CubeFace::Down => ((x + 1.0).rem_euclid(2.0), (z + 1.0).rem_euclid(2.0)),
+    };
+    (face, u / 2.0, v / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    fn point(x: f64, y: f64, z: f64) -> Tuple {
+        Tuple::set_point(x, y, z)
+    }
+
+    fn assert_uv(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 0.0001 && (actual.1 - expected.1).abs() < 0.0001,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_spherical_mapping() {
+        let cases = [
+            (point(0.0, 0.0, -1.0), (0.0, 0.5)),
+            (point(1.0, 0.0, 0.0), (0.25, 0.5)),
+            (point(0.0, 0.0, 1.0), (0.5, 0.5)),
+            (point(-1.0, 0.0, 0.0), (0.75, 0.5)),
+            (point(0.0, 1.0, 0.0), (0.5, 1.0)),
+            (point(0.0, -1.0, 0.0), (0.5, 0.0)),
+            (point(FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0), (0.25, 0.75)),
+        ];
+        for (p, expected) in cases {
+            assert_uv(spherical_map(&p), expected);
+        }
+    }
+
+    #[test]
+    fn test_planar_mapping() {
+        let cases = [
+            (point(0.25, 0.0, 0.5), (0.25, 0.5)),
+            (point(0.25, 0.0, -0.25), (0.25, 0.75)),
+            (point(0.25, 0.5, -0.25), (0.25, 0.75)),
+            (point(1.25, 0.0, 0.5), (0.25, 0.5)),
+            (point(0.25, 0.0, -1.75), (0.25, 0.25)),
+            (point(1.0, 0.0, -1.0), (0.0, 0.0)),
+            (point(0.0, 0.0, 0.0), (0.0, 0.0)),
+        ];
+        for (p, expected) in cases {
+            assert_uv(planar_map(&p), expected);
+        }
+    }
+
+    #[test]
+    fn test_cylindrical_mapping() {
+        let cases = [
+            (point(0.0, 0.0, -1.0), (0.0, 0.0)),
+            (point(0.0, 0.5, -1.0), (0.0, 0.5)),
+            (point(0.0, 1.0, -1.0), (0.0, 0.0)),
+            (point(FRAC_1_SQRT_2, 0.5, -FRAC_1_SQRT_2), (0.125, 0.5)),
+            (point(1.0, 0.5, 0.0), (0.25, 0.5)),
+            (point(FRAC_1_SQRT_2, 0.5, FRAC_1_SQRT_2), (0.375, 0.5)),
+            (point(0.0, -0.25, 1.0), (0.5, 0.75)),
+            (point(-FRAC_1_SQRT_2, 0.5, FRAC_1_SQRT_2), (0.625, 0.5)),
+            (point(-1.0, 1.25, 0.0), (0.75, 0.25)),
+            (point(-FRAC_1_SQRT_2, 0.5, -FRAC_1_SQRT_2), (0.875, 0.5)),
+        ];
+        for (p, expected) in cases {
+            assert_uv(cylindrical_map(&p), expected);
+        }
+    }
+
+    #[test]
+    fn test_cube_faces() {
+        assert_eq!(face_from_point(&point(-1.0, 0.5, -0.25)), CubeFace::Left);
+        assert_eq!(face_from_point(&point(1.1, -0.75, 0.8)), CubeFace::Right);
+        assert_eq!(face_from_point(&point(0.1, 0.6, 0.9)), CubeFace::Front);
+        assert_eq!(face_from_point(&point(-0.7, 0.0, -2.0)), CubeFace::Back);
+        assert_eq!(face_from_point(&point(0.5, 1.0, 0.9)), CubeFace::Up);
+        assert_eq!(face_from_point(&point(-0.2, -1.3, 1.1)), CubeFace::Down);
+    }
+
+    #[test]
+    fn test_cube_face_uv() {
+        let cases = [
+            (point(-0.5, 0.5, 1.0), CubeFace::Front, (0.25, 0.75)),
+            (point(0.5, -0.5, 1.0), CubeFace::Front, (0.75, 0.25)),
+            (point(0.5, 0.5, -1.0), CubeFace::Back, (0.25, 0.75)),
+            (point(-0.5, -0.5, -1.0), CubeFace::Back, (0.75, 0.25)),
+            (point(-1.0, 0.5, -0.5), CubeFace::Left, (0.25, 0.75)),
+            (point(-1.0, -0.5, 0.5), CubeFace::Left, (0.75, 0.25)),
+            (point(1.0, 0.5, 0.5), CubeFace::Right, (0.25, 0.75)),
+            (point(1.0, -0.5, -0.5), CubeFace::Right, (0.75, 0.25)),
+            (point(-0.5, 1.0, -0.5), CubeFace::Up, (0.25, 0.75)),
+            (point(0.5, 1.0, 0.5), CubeFace::Up, (0.75, 0.25)),
+            (point(-0.5, -1.0, 0.5), CubeFace::Down, (0.25, 0.75)),
+            (point(0.5, -1.0, -0.5), CubeFace::Down, (0.75, 0.25)),
+        ];
+        for (p, face, expected) in cases {
+            let (actual_face, u, v) = cube_map(&p);
+            assert_eq!(actual_face, face);
+            assert_uv((u, v), expected);
+        }
+    }
+
+    #[test]
+    fn test_cube_mapping_uses_a_strip() {
+        //the right face is third in the strip
+        assert_uv(
+            UvMapping::Cube.map(&point(1.0, 0.5, 0.5)),
+            ((2.0 + 0.25) / 6.0, 0.75),
+        );
+        assert_uv(UvMapping::Spherical.map(&point(1.0, 0.0, 0.0)), (0.25, 0.5));
+    }
+}
@@ -0,0 +1,695 @@
+#![allow(dead_code)]
+use crate::bvh::Bvh;
+use crate::color::color;
+use crate::color::Color;
+use crate::intersection::hit;
+use crate::intersection::Computations;
+use crate::intersection::Intersection;
+use crate::light::lighting;
+use crate::light::PointLight;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::shape::Shape;
+use crate::sphere::Sphere;
+use crate::tuple::Tuple;
+use std::sync::OnceLock;
+
+//how many times a ray may bounce off reflective surfaces before giving up
+pub const DEFAULT_MAX_DEPTH: usize = 5;
+
+#[derive(Debug)]
+pub struct World {
+    objects: Vec<Box<dyn Shape>>,
+    lights: Vec<PointLight>,
+    max_depth: usize,
+    //built on the first intersection, dropped whenever objects are added
+    bvh: OnceLock<Bvh>,
+}
+
+impl World {
+    pub fn new() -> World {
+        World {
+            objects: Vec::new(),
+            lights: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            bvh: OnceLock::new(),
+        }
+    }
+
+    //two concentric spheres lit from the upper left, handy for tests
+    pub fn default_world() -> World {
+        let mut outer = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        let mut outer_material = Material::default_material();
+        outer_material.color = color(0.8, 1.0, 0.6);
+        outer_material.diffuse = 0.7;
+        outer_material.specular = 0.2;
+        outer.material = outer_material;
+
+        let inner = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 0.5);
+
+        let light =
+            PointLight::set_point_light(color(1.0, 1.0, 1.0), Tuple::set_point(-10.0, 10.0, -10.0));
+
+        let mut world = World::new();
+        world.add_object(Box::new(outer));
+        world.add_object(Box::new(inner));
+        world.add_light(light);
+        world
+    }
+
+    pub fn add_object(&mut self, object: Box<dyn Shape>) {
+        self.objects.push(object);
+        self.bvh = OnceLock::new();
+    }
+
+    pub fn add_light(&mut self, light: PointLight) {
+        self.lights.push(light);
+    }
+
+    pub fn objects(&self) -> &[Box<dyn Shape>] {
+        &self.objects
+    }
+
+    pub fn lights(&self) -> &[PointLight] {
+        &self.lights
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    //every intersection of the ray with every object, sorted by t
+    pub fn intersect_world(&self, ray: &Ray) -> Vec<Intersection<'_>> {
+        let bvh = self.bvh.get_or_init(|| Bvh::build(&self.objects));
+        bvh.intersect(&self.objects, ray)
+    }
+
+    //each light contributes on top of the others, remaining is how many bounces are left
+    pub fn shade_hit(&self, comps: &Computations, remaining: usize) -> Color {
+        let mut shade = Color::create_black();
+        for light in &self.lights {
+            let in_shadow = self.is_shadowed(light, &comps.over_point);
+            shade = shade
+                + lighting(
+                    comps.object.material(),
+                    comps.object,
+                    light.clone(),
+                    comps.over_point.clone(),
+                    comps.eye_vector.clone(),
+                    comps.normal_vector.clone(),
+                    in_shadow,
+                );
+        }
+        let reflected = self.reflected_color(comps, remaining);
+        let refracted = self.refracted_color(comps, remaining);
+
+        let material = comps.object.material();
+        if material.reflective > 0.0 && material.transparency > 0.0 {
+            let reflectance = comps.schlick();
+            return shade + reflected * reflectance + refracted * (1.0 - reflectance);
+        }
+        shade + reflected + refracted
+    }
+
+    pub fn reflected_color(&self, comps: &Computations, remaining: usize) -> Color {
+        let reflective = comps.object.material().reflective;
+        if remaining == 0 || reflective == 0.0 {
+            return Color::create_black();
+        }
+        let reflect_ray = Ray::set_ray(comps.reflect_vector.clone(), comps.over_point.clone());
+        self.color_at_depth(&reflect_ray, remaining - 1) * reflective
+    }
+
+    pub fn refracted_color(&self, comps: &Computations, remaining: usize) -> Color {
+        let transparency = comps.object.material().transparency;
+        if remaining == 0 || transparency == 0.0 {
+            return Color::create_black();
+        }
+
+        //snell's law, bail out on total internal reflection
+        let n_ratio = comps.n1 / comps.n2;
+        let cos_i = Tuple::dot(comps.eye_vector.clone(), comps.normal_vector.clone());
+        let sin2_t = n_ratio * n_ratio * (1.0 - cos_i * cos_i);
+        if sin2_t > 1.0 {
+            return Color::create_black();
+        }
+
+        let cos_t = (1.0 - sin2_t).sqrt();
+        let direction = comps.normal_vector.clone() * (n_ratio * cos_i - cos_t)
+            - comps.eye_vector.clone() * n_ratio;
+        let refract_ray = Ray::set_ray(direction, comps.under_point.clone());
+        self.color_at_depth(&refract_ray, remaining - 1) * transparency
+    }
+
+    //true when something sits between the point and the light
+    pub fn is_shadowed(&self, light: &PointLight, point: &Tuple) -> bool {
+        let to_light = light.position.clone() - point.clone();
+        let distance = to_light.magnitude();
+        let shadow_ray = Ray::set_ray(to_light.normalize(), point.clone());
+        let xs = self.intersect_world(&shadow_ray);
+        match hit(&xs) {
+            Some(nearest) => nearest.t < distance,
+            None => false,
+        }
+    }
+
+    pub fn color_at(&self, ray: &Ray) -> Color {
+        self.color_at_depth(ray, self.max_depth)
+    }
+
+    pub fn color_at_depth(&self, ray: &Ray, remaining: usize) -> Color {
+        let xs = self.intersect_world(ray);
+        match hit(&xs) {
+            Some(nearest) => self.shade_hit(&nearest.prepare_computations(ray, &xs), remaining),
+            None => Color::create_black(),
+        }
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        World::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intersection::Intersection;
+    use crate::plane::Plane;
+    use crate::sphere::glass_sphere;
+    use crate::transform::translation;
+
+    #[test]
+    fn test_empty_world() {
+        let world = World::new();
+        assert_eq!(world.objects().len(), 0);
+        assert_eq!(world.lights().len(), 0);
+    }
+
+    #[test]
+    fn test_default_world() {
+        let world = World::default_world();
+        assert_eq!(world.objects().len(), 2);
+        assert_eq!(world.lights().len(), 1);
+        assert_eq!(world.objects()[0].material().color, color(0.8, 1.0, 0.6));
+    }
+
+    #[test]
+    fn test_intersect_world_sorted() {
+        let world = World::default_world();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let xs = world.intersect_world(&ray);
+        let ts: Vec<f64> = xs.iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![4.0, 4.5, 5.5, 6.0]);
+    }
+
+    #[test]
+    fn test_intersect_world_miss() {
+        let world = World::default_world();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        assert_eq!(world.intersect_world(&ray).len(), 0);
+    }
+
+    #[test]
+    fn test_hit_from_inside_world() {
+        let world = World::default_world();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, 0.0),
+        );
+        let xs = world.intersect_world(&ray);
+        let nearest = hit(&xs).unwrap();
+        assert_eq!(nearest.t, 0.5);
+        assert_eq!(nearest.object.material().color, color(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_shade_hit_outside() {
+        let world = World::default_world();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let shape = world.objects()[0].as_ref();
+        let i = Intersection::new(4.0, shape);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert_eq!(
+            world.shade_hit(&comps, DEFAULT_MAX_DEPTH),
+            color(0.38066, 0.47583, 0.2855)
+        );
+    }
+
+    #[test]
+    fn test_shade_hit_inside() {
+        let mut world = World::new();
+        world.add_object(Box::new(Sphere::set_sphere(
+            Tuple::set_point(0.0, 0.0, 0.0),
+            0.5,
+        )));
+        world.add_light(PointLight::set_point_light(
+            color(1.0, 1.0, 1.0),
+            Tuple::set_point(0.0, 0.25, 0.0),
+        ));
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, 0.0),
+        );
+        let shape = world.objects()[0].as_ref();
+        let i = Intersection::new(0.5, shape);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert_eq!(
+            world.shade_hit(&comps, DEFAULT_MAX_DEPTH),
+            color(1.00151, 1.00151, 1.00151)
+        );
+    }
+
+    #[test]
+    fn test_color_at_miss() {
+        let world = World::default_world();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        assert_eq!(world.color_at(&ray), color(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_color_at_hit() {
+        let world = World::default_world();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        assert_eq!(world.color_at(&ray), color(0.38066, 0.47583, 0.2855));
+    }
+
+    #[test]
+    fn test_color_at_behind_ray() {
+        let mut world = World::new();
+        let mut outer = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        outer.material.ambient = 1.0;
+        let mut inner = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 0.5);
+        inner.material.ambient = 1.0;
+        inner.material.color = color(0.2, 0.4, 0.6);
+        world.add_object(Box::new(outer));
+        world.add_object(Box::new(inner));
+        world.add_light(PointLight::set_point_light(
+            color(1.0, 1.0, 1.0),
+            Tuple::set_point(-10.0, 10.0, -10.0),
+        ));
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, -1.0),
+            Tuple::set_point(0.0, 0.0, 0.75),
+        );
+        assert_eq!(world.color_at(&ray), color(0.2, 0.4, 0.6));
+    }
+
+    #[test]
+    fn test_no_shadow_nothing_collinear() {
+        let world = World::default_world();
+        let point = Tuple::set_point(0.0, 10.0, 0.0);
+        assert!(!world.is_shadowed(&world.lights()[0], &point));
+    }
+
+    #[test]
+    fn test_shadow_object_between_point_and_light() {
+        let world = World::default_world();
+        let point = Tuple::set_point(10.0, -10.0, 10.0);
+        assert!(world.is_shadowed(&world.lights()[0], &point));
+    }
+
+    #[test]
+    fn test_no_shadow_object_behind_light() {
+        let world = World::default_world();
+        let point = Tuple::set_point(-20.0, 20.0, -20.0);
+        assert!(!world.is_shadowed(&world.lights()[0], &point));
+    }
+
+    #[test]
+    fn test_no_shadow_object_behind_point() {
+        let world = World::default_world();
+        let point = Tuple::set_point(-2.0, 2.0, -2.0);
+        assert!(!world.is_shadowed(&world.lights()[0], &point));
+    }
+
+    #[test]
+    fn test_shade_hit_in_shadow() {
+        let mut world = World::new();
+        world.add_light(PointLight::set_point_light(
+            color(1.0, 1.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -10.0),
+        ));
+        world.add_object(Box::new(Sphere::set_sphere(
+            Tuple::set_point(0.0, 0.0, 0.0),
+            1.0,
+        )));
+        let mut shadowed = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        shadowed.set_transform(translation(0.0, 0.0, 10.0));
+        world.add_object(Box::new(shadowed));
+
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, 5.0),
+        );
+        let shape = world.objects()[1].as_ref();
+        let i = Intersection::new(4.0, shape);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert_eq!(
+            world.shade_hit(&comps, DEFAULT_MAX_DEPTH),
+            color(0.1, 0.1, 0.1)
+        );
+    }
+
+    //a black mirror in front of the camera and a flat colored sphere behind it
+    fn mirror_world(reflective: f64) -> World {
+        let mut world = World::new();
+        let mut mirror = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        mirror.material.color = color(0.0, 0.0, 0.0);
+        mirror.material.ambient = 0.0;
+        mirror.material.diffuse = 0.0;
+        mirror.material.specular = 0.0;
+        mirror.material.reflective = reflective;
+        let mut behind = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, -10.0), 1.0);
+        behind.material.color = color(0.2, 0.4, 0.6);
+        behind.material.ambient = 1.0;
+        behind.material.diffuse = 0.0;
+        behind.material.specular = 0.0;
+        world.add_object(Box::new(mirror));
+        world.add_object(Box::new(behind));
+        world.add_light(PointLight::set_point_light(
+            color(1.0, 1.0, 1.0),
+            Tuple::set_point(-10.0, 10.0, -10.0),
+        ));
+        world
+    }
+
+    #[test]
+    fn test_reflected_color_nonreflective() {
+        let world = World::default_world();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, 0.0),
+        );
+        let shape = world.objects()[1].as_ref();
+        let i = Intersection::new(0.5, shape);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert_eq!(
+            world.reflected_color(&comps, DEFAULT_MAX_DEPTH),
+            color(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_reflected_color_reflective() {
+        let world = mirror_world(0.5);
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let shape = world.objects()[0].as_ref();
+        let i = Intersection::new(4.0, shape);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert_eq!(
+            world.reflected_color(&comps, DEFAULT_MAX_DEPTH),
+            color(0.1, 0.2, 0.3)
+        );
+        assert_eq!(world.color_at(&ray), color(0.1, 0.2, 0.3));
+    }
+
+    #[test]
+    fn test_reflected_color_no_bounces_left() {
+        let world = mirror_world(1.0);
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let shape = world.objects()[0].as_ref();
+        let i = Intersection::new(4.0, shape);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert_eq!(world.reflected_color(&comps, 0), color(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_max_depth_is_configurable() {
+        let mut world = mirror_world(1.0);
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        assert_eq!(world.max_depth(), DEFAULT_MAX_DEPTH);
+        assert_eq!(world.color_at(&ray), color(0.2, 0.4, 0.6));
+        world.set_max_depth(0);
+        assert_eq!(world.color_at(&ray), color(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_color_at_mutually_reflective_surfaces() {
+        let mut world = World::new();
+        let mut lower = Sphere::set_sphere(Tuple::set_point(0.0, -3.0, 0.0), 1.0);
+        lower.material.reflective = 1.0;
+        let mut upper = Sphere::set_sphere(Tuple::set_point(0.0, 3.0, 0.0), 1.0);
+        upper.material.reflective = 1.0;
+        world.add_object(Box::new(lower));
+        world.add_object(Box::new(upper));
+        world.add_light(PointLight::set_point_light(
+            color(1.0, 1.0, 1.0),
+            Tuple::set_point(0.0, 0.0, 0.0),
+        ));
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_point(0.0, 0.0, 0.0),
+        );
+        //terminating at all is the point of this test
+        let _ = world.color_at(&ray);
+    }
+
+    #[test]
+    fn test_refracted_color_opaque() {
+        let world = World::default_world();
+        let shape = world.objects()[0].as_ref();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let xs = vec![Intersection::new(4.0, shape), Intersection::new(6.0, shape)];
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        assert_eq!(
+            world.refracted_color(&comps, DEFAULT_MAX_DEPTH),
+            color(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_refracted_color_no_bounces_left() {
+        let mut world = World::new();
+        world.add_object(Box::new(glass_sphere()));
+        let shape = world.objects()[0].as_ref();
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let xs = vec![Intersection::new(4.0, shape), Intersection::new(6.0, shape)];
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        assert_eq!(world.refracted_color(&comps, 0), color(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_refracted_color_total_internal_reflection() {
+        let mut world = World::new();
+        world.add_object(Box::new(glass_sphere()));
+        let shape = world.objects()[0].as_ref();
+        let root = 2.0_f64.sqrt() / 2.0;
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 1.0, 0.0),
+            Tuple::set_point(0.0, 0.0, root),
+        );
+        let xs = vec![
+            Intersection::new(-root, shape),
+            Intersection::new(root, shape),
+        ];
+        let comps = xs[1].prepare_computations(&ray, &xs);
+        assert_eq!(
+            world.refracted_color(&comps, DEFAULT_MAX_DEPTH),
+            color(0.0, 0.0, 0.0)
+        );
+    }
+
+    //an invisible pane with a refractive index of 1.0 and a flat colored sphere behind it
+    fn window_world(front: Sphere) -> World {
+        let mut world = World::new();
+        let mut behind = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 10.0), 1.0);
+        behind.material.color = color(0.2, 0.4, 0.6);
+        behind.material.ambient = 1.0;
+        behind.material.diffuse = 0.0;
+        behind.material.specular = 0.0;
+        world.add_object(Box::new(front));
+        world.add_object(Box::new(behind));
+        world.add_light(PointLight::set_point_light(
+            color(1.0, 1.0, 1.0),
+            Tuple::set_point(-10.0, 10.0, -10.0),
+        ));
+        world
+    }
+
+    fn invisible_material(sphere: &mut Sphere) {
+        sphere.material.color = color(0.0, 0.0, 0.0);
+        sphere.material.ambient = 0.0;
+        sphere.material.diffuse = 0.0;
+        sphere.material.specular = 0.0;
+    }
+
+    #[test]
+    fn test_refracted_color_passes_through() {
+        let mut pane = glass_sphere();
+        invisible_material(&mut pane);
+        pane.material.refractive_index = 1.0;
+        let world = window_world(pane);
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        assert_eq!(world.color_at(&ray), color(0.2, 0.4, 0.6));
+    }
+
+    #[test]
+    fn test_shade_hit_transparent_material() {
+        let mut pane = glass_sphere();
+        invisible_material(&mut pane);
+        pane.material.transparency = 0.5;
+        pane.material.refractive_index = 1.0;
+        let world = window_world(pane);
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let xs = world.intersect_world(&ray);
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        //half the light gets through the front face and half of that through the back
+        assert_eq!(
+            world.shade_hit(&comps, DEFAULT_MAX_DEPTH),
+            color(0.05, 0.1, 0.15)
+        );
+    }
+
+    #[test]
+    fn test_shade_hit_schlick_blend() {
+        let mut world = World::new();
+        let mut glass = glass_sphere();
+        invisible_material(&mut glass);
+        glass.material.reflective = 1.0;
+        let mut red = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, -10.0), 1.0);
+        red.material.color = color(1.0, 0.0, 0.0);
+        red.material.ambient = 1.0;
+        red.material.diffuse = 0.0;
+        red.material.specular = 0.0;
+        world.add_object(Box::new(glass));
+        world.add_object(Box::new(red));
+        world.add_light(PointLight::set_point_light(
+            color(1.0, 1.0, 1.0),
+            Tuple::set_point(-10.0, 10.0, -10.0),
+        ));
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, 0.0, 1.0),
+            Tuple::set_point(0.0, 0.0, -5.0),
+        );
+        let xs = world.intersect_world(&ray);
+        let nearest = hit(&xs).unwrap();
+        let comps = nearest.prepare_computations(&ray, &xs);
+        //head on into glass only 4% is reflected, the refracted path is black with one bounce
+        assert_eq!(world.shade_hit(&comps, 1), color(0.04, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reflected_color_reflective_plane() {
+        let mut world = World::default_world();
+        let mut floor = Plane::set_plane();
+        floor.material.reflective = 0.5;
+        floor.set_transform(translation(0.0, -1.0, 0.0));
+        world.add_object(Box::new(floor));
+
+        let root = 2.0_f64.sqrt() / 2.0;
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, -root, root),
+            Tuple::set_point(0.0, 0.0, -3.0),
+        );
+        let shape = world.objects()[2].as_ref();
+        let i = Intersection::new(2.0_f64.sqrt(), shape);
+        let comps = i.prepare_computations(&ray, &[i]);
+        assert_eq!(
+            world.reflected_color(&comps, DEFAULT_MAX_DEPTH),
+            color(0.19154, 0.23913, 0.14396)
+        );
+        assert_eq!(
+            world.shade_hit(&comps, DEFAULT_MAX_DEPTH),
+            color(0.87797, 0.92555, 0.83038)
+        );
+    }
+
+    #[test]
+    fn test_shade_hit_transparent_plane() {
+        let mut world = World::default_world();
+        let mut floor = Plane::set_plane();
+        floor.set_transform(translation(0.0, -1.0, 0.0));
+        floor.material.transparency = 0.5;
+        floor.material.refractive_index = 1.5;
+        let mut ball = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        ball.material.color = color(1.0, 0.0, 0.0);
+        ball.material.ambient = 0.5;
+        ball.set_transform(translation(0.0, -3.5, -0.5));
+        world.add_object(Box::new(floor));
+        world.add_object(Box::new(ball));
+
+        let root = 2.0_f64.sqrt() / 2.0;
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, -root, root),
+            Tuple::set_point(0.0, 0.0, -3.0),
+        );
+        let shape = world.objects()[2].as_ref();
+        let xs = vec![Intersection::new(2.0_f64.sqrt(), shape)];
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        assert_eq!(
+            world.shade_hit(&comps, DEFAULT_MAX_DEPTH),
+            color(0.93642, 0.68642, 0.68642)
+        );
+    }
+
+    #[test]
+    fn test_shade_hit_reflective_transparent_plane() {
+        let mut world = World::default_world();
+        let mut floor = Plane::set_plane();
+        floor.set_transform(translation(0.0, -1.0, 0.0));
+        floor.material.reflective = 0.5;
+        floor.material.transparency = 0.5;
+        floor.material.refractive_index = 1.5;
+        let mut ball = Sphere::set_sphere(Tuple::set_point(0.0, 0.0, 0.0), 1.0);
+        ball.material.color = color(1.0, 0.0, 0.0);
+        ball.material.ambient = 0.5;
+        ball.set_transform(translation(0.0, -3.5, -0.5));
+        world.add_object(Box::new(floor));
+        world.add_object(Box::new(ball));
+
+        let root = 2.0_f64.sqrt() / 2.0;
+        let ray = Ray::set_ray(
+            Tuple::set_vector(0.0, -root, root),
+            Tuple::set_point(0.0, 0.0, -3.0),
+        );
+        let shape = world.objects()[2].as_ref();
+        let xs = vec![Intersection::new(2.0_f64.sqrt(), shape)];
+        let comps = xs[0].prepare_computations(&ray, &xs);
+        assert_eq!(
+            world.shade_hit(&comps, DEFAULT_MAX_DEPTH),
+            color(0.93391, 0.69643, 0.69243)
+        );
+    }
+}